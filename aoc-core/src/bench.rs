@@ -0,0 +1,104 @@
+use std::{
+    fmt,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::solution::Solution;
+
+/// Summary of repeated timings of one step.
+#[derive(Debug, Clone, Copy)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub std_dev: Duration,
+}
+
+impl Stats {
+    /// Panics if `samples` is empty.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "need at least one sample");
+        samples.sort();
+
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+
+        let secs = samples
+            .iter()
+            .map(Duration::as_secs_f64)
+            .collect::<Vec<_>>();
+        let mean = secs.iter().sum::<f64>() / n as f64;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n as f64;
+
+        Self {
+            samples: n,
+            min: samples[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>10.3?} (min {:.3?}, mean {:.3?} ± {:.3?}, n={})",
+            self.median, self.min, self.mean, self.std_dev, self.samples
+        )
+    }
+}
+
+/// Timings for each step of a day, measured separately.
+#[derive(Debug, Clone)]
+pub struct Timings {
+    pub parse: Stats,
+    /// Or why the part failed.
+    pub part_one: Result<Stats, String>,
+    /// Or why the part failed.
+    pub part_two: Result<Stats, String>,
+}
+
+/// Times parsing and both parts `samples` times each. Fails only if the
+/// input doesn't parse; a part that fails is reported in the [`Timings`],
+/// and the other is still timed.
+pub fn time_solution<S: Solution>(input: &str, samples: usize) -> miette::Result<Timings> {
+    let parsed = S::parse(input)?;
+    let parse = time_samples(samples, || S::parse(black_box(input)))?;
+    let part_one = time_samples(samples, || S::part_one(black_box(&parsed)));
+    let part_two = time_samples(samples, || S::part_two(black_box(&parsed)));
+
+    Ok(Timings {
+        parse,
+        part_one: part_one.map_err(|e| e.to_string()),
+        part_two: part_two.map_err(|e| e.to_string()),
+    })
+}
+
+/// Times `f` `samples` times, after one untimed warm-up run (which also
+/// surfaces any error).
+fn time_samples<T>(
+    samples: usize,
+    mut f: impl FnMut() -> miette::Result<T>,
+) -> miette::Result<Stats> {
+    f()?;
+    let durations = (0..samples)
+        .map(|_| time(&mut f))
+        .collect::<miette::Result<Vec<_>>>()?;
+    Ok(Stats::from_samples(durations))
+}
+
+fn time<T>(f: impl FnOnce() -> miette::Result<T>) -> miette::Result<Duration> {
+    let start = Instant::now();
+    let res = f()?;
+    let elapsed = start.elapsed();
+    drop(black_box(res));
+    Ok(elapsed)
+}
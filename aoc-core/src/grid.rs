@@ -1,7 +1,7 @@
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
-pub(crate) struct GridCoord {
-    pub(crate) x: usize,
-    pub(crate) y: usize,
+pub struct GridCoord {
+    pub x: usize,
+    pub y: usize,
 }
 
 impl std::fmt::Debug for GridCoord {
@@ -11,12 +11,12 @@ impl std::fmt::Debug for GridCoord {
 }
 
 impl From<(usize, usize)> for GridCoord {
-    fn from((x,y): (usize, usize)) -> Self {
+    fn from((x, y): (usize, usize)) -> Self {
         Self { x, y }
     }
 }
 
-pub(crate) struct Grid<T> {
+pub struct Grid<T> {
     width: usize,
     height: usize,
     data: Vec<T>,
@@ -26,7 +26,7 @@ impl<T> Grid<T>
 where
     T: Default + Clone,
 {
-    pub(crate) fn new(width: usize, height: usize) -> Self {
+    pub fn new(width: usize, height: usize) -> Self {
         Self {
             width,
             height,
@@ -34,19 +34,19 @@ where
         }
     }
 
-    fn in_bounds(&self, coord: GridCoord) -> bool {
+    pub fn in_bounds(&self, coord: GridCoord) -> bool {
         coord.x < self.width && coord.y < self.height
     }
 
-    pub(crate) fn cell_mut(&mut self, coord: GridCoord) -> Option<&mut T> {
+    pub fn cell_mut(&mut self, coord: GridCoord) -> Option<&mut T> {
         if !self.in_bounds(coord) {
             return None;
         }
 
-        Some(&mut self.data[coord.y * self.width + coord. x])
+        Some(&mut self.data[coord.y * self.width + coord.x])
     }
 
-    pub(crate) fn cell(&self, coord: GridCoord) -> Option<&T> {
+    pub fn cell(&self, coord: GridCoord) -> Option<&T> {
         if !self.in_bounds(coord) {
             return None;
         }
@@ -54,11 +54,17 @@ where
         Some(&self.data[coord.y * self.width + coord.x])
     }
 
-    pub(crate) fn width(&self) -> usize {
+    pub fn width(&self) -> usize {
         self.width
     }
 
-    pub(crate) fn height(&self) -> usize {
+    pub fn height(&self) -> usize {
         self.height
     }
-}
\ No newline at end of file
+
+    /// Every coordinate in the grid, row by row.
+    pub fn coords(&self) -> impl Iterator<Item = GridCoord> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| GridCoord::from((x, y))))
+    }
+}
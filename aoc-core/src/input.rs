@@ -0,0 +1,41 @@
+use std::io;
+
+#[derive(thiserror::Error, Debug, miette::Diagnostic)]
+#[error("could not read input from {path}")]
+pub struct InputError {
+    path: String,
+    #[source]
+    source: io::Error,
+}
+
+/// Reads the puzzle input from `path`, or from stdin if `path` is `-`.
+pub fn read_input_from(path: &str) -> Result<String, InputError> {
+    let res = if path == "-" {
+        io::read_to_string(io::stdin())
+    } else {
+        std::fs::read_to_string(path)
+    };
+
+    res.map_err(|source| InputError {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+/// Reads the puzzle input from the path given as the first argument (`-` for
+/// stdin), falling back to `default_path`.
+pub fn read_input(default_path: &str) -> Result<String, InputError> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| default_path.to_owned());
+    read_input_from(&path)
+}
+
+/// Calls [`read_input`] with the calling crate's bundled `src/input.txt` as the
+/// default.
+#[macro_export]
+macro_rules! input {
+    () => {
+        $crate::input::read_input(concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt"))
+    };
+}
@@ -0,0 +1,11 @@
+//! Pieces shared between the individual days: grids, positions, ranges,
+//! parsing helpers, input loading, the [`Solution`](solution::Solution)
+//! trait every day implements and the timing used to benchmark it.
+
+pub mod bench;
+pub mod grid;
+pub mod input;
+pub mod parse;
+pub mod pos;
+pub mod range;
+pub mod solution;
@@ -0,0 +1,173 @@
+use std::fmt;
+
+use miette::{Diagnostic, LabeledSpan, SourceCode};
+use nom::{
+    bytes::complete::take_while1,
+    combinator::{all_consuming, map_res},
+    Finish, IResult, Offset, Parser,
+};
+use nom_supreme::{
+    error::{ErrorTree, GenericErrorTree},
+    final_parser::final_parser,
+};
+
+/// What the days' parsers return. An [`ErrorTree`] remembers what was expected
+/// where, which [`BadInput`] turns into a labelled report.
+pub type ParseResult<'a, O> = IResult<&'a str, O, ErrorTree<&'a str>>;
+
+/// Input that failed to parse, reported with the offending parts of the input
+/// labelled (and their line and column shown).
+#[derive(thiserror::Error, Debug)]
+#[error("bad input")]
+pub struct BadInput {
+    src: String,
+    labels: Vec<LabeledSpan>,
+    help: Option<String>,
+}
+
+impl BadInput {
+    /// Points at `bad`, which must be a slice of `src`.
+    pub fn at(src: &str, bad: &str, label: impl fmt::Display) -> Self {
+        Self {
+            src: src.to_owned(),
+            labels: vec![LabeledSpan::new(
+                Some(label.to_string()),
+                src.offset(bad),
+                bad.len(),
+            )],
+            help: None,
+        }
+    }
+
+    /// Points at several places in `src` at once, for input that parsed but
+    /// doesn't make sense.
+    pub fn with_labels(src: &str, labels: Vec<LabeledSpan>) -> Self {
+        Self {
+            src: src.to_owned(),
+            labels,
+            help: None,
+        }
+    }
+
+    /// Points at where a parser gave up, `tree` having byte offsets into
+    /// `src` as its locations. Every context the failure happened in and
+    /// every alternative that was tried gets a label of its own.
+    pub fn from_tree<T, C, E>(src: &str, tree: GenericErrorTree<usize, T, C, E>) -> Self
+    where
+        T: fmt::Debug,
+        C: fmt::Debug,
+        E: fmt::Display,
+    {
+        let mut res = Self {
+            src: src.to_owned(),
+            labels: vec![],
+            help: None,
+        };
+        res.add_tree(&tree);
+        res
+    }
+
+    fn add_tree<T, C, E>(&mut self, tree: &GenericErrorTree<usize, T, C, E>)
+    where
+        T: fmt::Debug,
+        C: fmt::Debug,
+        E: fmt::Display,
+    {
+        match tree {
+            GenericErrorTree::Base { location, kind } => self.add_label(*location, kind),
+            GenericErrorTree::Stack { base, contexts } => {
+                self.add_tree(base);
+                for (location, context) in contexts {
+                    self.add_label(*location, context);
+                }
+            }
+            GenericErrorTree::Alt(alts) => {
+                if self.help.is_none() {
+                    self.help = Some(format!(
+                        "none of the {} alternatives matched; each is labelled",
+                        alts.len()
+                    ));
+                }
+                for alt in alts {
+                    self.add_tree(alt);
+                }
+            }
+        }
+    }
+
+    fn add_label(&mut self, offset: usize, label: impl fmt::Display) {
+        let label = LabeledSpan::new(Some(label.to_string()), offset, 0);
+        // Alternatives often fail the same way at the same place.
+        if !self.labels.contains(&label) {
+            self.labels.push(label);
+        }
+    }
+}
+
+impl Diagnostic for BadInput {
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        Some(&self.src)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        Some(Box::new(self.labels.iter().cloned()))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.help
+            .as_ref()
+            .map(|help| Box::new(help) as Box<dyn fmt::Display>)
+    }
+}
+
+pub fn parse_number(i: &str) -> ParseResult<'_, usize> {
+    map_res(take_while1(|c: char| c.is_ascii_digit()), |s: &str| {
+        s.parse::<usize>()
+    })(i)
+}
+
+/// Runs `parser` over the whole of `input`.
+pub fn parse_all<'a, O, P>(input: &'a str, parser: P) -> Result<O, BadInput>
+where
+    P: Parser<&'a str, O, ErrorTree<&'a str>>,
+{
+    final_parser(parser)(input).map_err(|e: ErrorTree<&str>| tree_error(input, e))
+}
+
+/// Runs `parser` over every line of `input`, each line having to be consumed
+/// entirely.
+pub fn parse_lines<'a, O, P>(
+    input: &'a str,
+    mut parser: P,
+) -> impl Iterator<Item = Result<O, BadInput>> + 'a
+where
+    O: 'a,
+    P: FnMut(&'a str) -> ParseResult<'a, O> + 'a,
+{
+    input.lines().map(move |line| {
+        all_consuming(&mut parser)(line)
+            .finish()
+            .map(|(_, o)| o)
+            .map_err(|e| tree_error(input, e))
+    })
+}
+
+/// Like [`parse_lines`] for line parsers that aren't nom parsers; a failing
+/// line is labelled with the error as a whole.
+pub fn try_parse_lines<'a, O, E, F>(
+    input: &'a str,
+    mut f: F,
+) -> impl Iterator<Item = Result<O, BadInput>> + 'a
+where
+    O: 'a,
+    E: fmt::Display,
+    F: FnMut(&'a str) -> Result<O, E> + 'a,
+{
+    input
+        .lines()
+        .map(move |line| f(line).map_err(|e| BadInput::at(input, line, e)))
+}
+
+fn tree_error(input: &str, e: ErrorTree<&str>) -> BadInput {
+    BadInput::from_tree(input, e.map_locations(|location| input.offset(location)))
+}
@@ -0,0 +1,24 @@
+use std::ops::RangeInclusive;
+
+pub trait InclusiveRangeExt {
+    fn contains_range(&self, other: &Self) -> bool;
+
+    fn completely_overlaps(&self, other: &Self) -> bool {
+        self.contains_range(other) || other.contains_range(self)
+    }
+
+    fn overlaps(&self, other: &Self) -> bool;
+}
+
+impl<T> InclusiveRangeExt for RangeInclusive<T>
+where
+    T: PartialOrd,
+{
+    fn contains_range(&self, other: &Self) -> bool {
+        self.contains(other.start()) && self.contains(other.end())
+    }
+
+    fn overlaps(&self, other: &Self) -> bool {
+        self.start() <= other.end() && other.start() <= self.end()
+    }
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+
+/// Which half of a day's puzzle to solve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// A day's puzzle. The input is parsed once and shared by both parts, each of
+/// which returns its answer as it would be submitted.
+pub trait Solution {
+    /// Day of the month the puzzle was published on.
+    const DAY: u8;
+
+    type Input;
+    type AnswerOne: fmt::Display;
+    type AnswerTwo: fmt::Display;
+
+    fn parse(input: &str) -> miette::Result<Self::Input>;
+
+    fn part_one(input: &Self::Input) -> miette::Result<Self::AnswerOne>;
+
+    fn part_two(input: &Self::Input) -> miette::Result<Self::AnswerTwo>;
+}
+
+/// Parses `input` and solves one part, rendering the answer as a string.
+pub fn solve<S: Solution>(part: Part, input: &str) -> miette::Result<String> {
+    let input = S::parse(input)?;
+    match part {
+        Part::One => S::part_one(&input).map(|answer| answer.to_string()),
+        Part::Two => S::part_two(&input).map(|answer| answer.to_string()),
+    }
+}
+
+/// Prints an answer, putting multi-line answers (such as CRT images) on their
+/// own lines.
+pub fn print_answer(day: u8, part: Part, answer: &str) {
+    if answer.contains('\n') {
+        println!("Day {day}, part {part}:\n{answer}");
+    } else {
+        println!("Day {day}, part {part}: {answer}");
+    }
+}
+
+/// Solves and prints both parts, as the individual day binaries do.
+pub fn print_answers<S: Solution>(input: &str) -> miette::Result<()> {
+    let input = S::parse(input)?;
+    print_answer(S::DAY, Part::One, &S::part_one(&input)?.to_string());
+    print_answer(S::DAY, Part::Two, &S::part_two(&input)?.to_string());
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use std::{collections::BTreeMap, path::Path, time::Duration};
+
+use aoc_core::bench::{Stats, Timings};
+use miette::IntoDiagnostic;
+use serde::{Deserialize, Serialize};
+
+/// Median timings of an earlier run, keyed by `dayN`, in nanoseconds.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Baseline(BTreeMap<String, BaselineEntry>);
+
+/// A part missing if it has never been timed successfully.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct BaselineEntry {
+    parse: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2: Option<u64>,
+}
+
+fn nanos(stats: &Stats) -> u64 {
+    stats.median.as_nanos() as u64
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> miette::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .into_diagnostic()
+            .map_err(|e| e.wrap_err(format!("could not read baseline {}", path.display())))?;
+        toml::from_str(&text)
+            .into_diagnostic()
+            .map_err(|e| e.wrap_err(format!("invalid baseline {}", path.display())))
+    }
+
+    /// Loads `path` if it exists, so saving a partial run keeps the other
+    /// days' numbers.
+    pub fn load_or_default(path: &Path) -> miette::Result<Self> {
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> miette::Result<()> {
+        let text = toml::to_string(self).into_diagnostic()?;
+        std::fs::write(path, text)
+            .into_diagnostic()
+            .map_err(|e| e.wrap_err(format!("could not write baseline {}", path.display())))
+    }
+
+    /// Keeps the earlier numbers for a part that failed this time.
+    pub fn record(&mut self, day: u8, timings: &Timings) {
+        let key = format!("day{day}");
+        let earlier = self.0.get(&key);
+        let entry = BaselineEntry {
+            parse: nanos(&timings.parse),
+            part1: match &timings.part_one {
+                Ok(stats) => Some(nanos(stats)),
+                Err(_) => earlier.and_then(|e| e.part1),
+            },
+            part2: match &timings.part_two {
+                Ok(stats) => Some(nanos(stats)),
+                Err(_) => earlier.and_then(|e| e.part2),
+            },
+        };
+        self.0.insert(key, entry);
+    }
+}
+
+/// Prints one day's timings, with the change in median against `baseline`
+/// where it has numbers for that day.
+pub fn report(day: u8, timings: &Timings, baseline: Option<&Baseline>) {
+    let previous = baseline.and_then(|b| b.0.get(&format!("day{day}")).copied());
+
+    println!("Day {day}");
+    for (label, stats, before) in [
+        ("parse", Ok(&timings.parse), previous.map(|p| p.parse)),
+        (
+            "part 1",
+            timings.part_one.as_ref(),
+            previous.and_then(|p| p.part1),
+        ),
+        (
+            "part 2",
+            timings.part_two.as_ref(),
+            previous.and_then(|p| p.part2),
+        ),
+    ] {
+        match (stats, before) {
+            (Ok(stats), Some(before)) => println!(
+                "  {label:<7}{stats}  {}",
+                change(Duration::from_nanos(before), stats.median)
+            ),
+            (Ok(stats), None) => println!("  {label:<7}{stats}"),
+            (Err(e), _) => println!("  {label:<7}failed: {e}"),
+        }
+    }
+}
+
+fn change(before: Duration, after: Duration) -> String {
+    if before.is_zero() {
+        return format!("was {before:.3?}");
+    }
+    let pct = (after.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0;
+    format!("{pct:+.1}% vs baseline {before:.3?}")
+}
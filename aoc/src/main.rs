@@ -0,0 +1,230 @@
+use std::path::PathBuf;
+
+use aoc_core::{
+    bench::{time_solution, Timings},
+    input::read_input_from,
+    solution::{print_answer, solve, Part, Solution},
+};
+use clap::{Parser, Subcommand};
+
+mod bench;
+mod verify;
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a day's puzzle and print the answer
+    Run {
+        /// Day of the month, 1 to 25
+        #[arg(long)]
+        day: u8,
+
+        /// Which part to solve; both if omitted
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Puzzle input file, or `-` for stdin; defaults to the day's bundled
+        /// `src/input.txt`
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Check every solution against the expected answers manifest
+    Verify {
+        /// Only check this day
+        #[arg(long)]
+        day: Option<u8>,
+
+        /// Expected answers file; defaults to `answers.toml` at the workspace
+        /// root
+        #[arg(long)]
+        manifest: Option<PathBuf>,
+    },
+    /// Time parsing and each part separately; build with `--release` for
+    /// meaningful numbers
+    Bench {
+        /// Only benchmark this day; all days otherwise
+        #[arg(long)]
+        day: Option<u8>,
+
+        /// Timed runs of each step
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        samples: u32,
+
+        /// Use each day's `src/example.txt` instead of `src/input.txt`
+        #[arg(long)]
+        examples: bool,
+
+        /// Compare medians against a baseline saved by an earlier run
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+
+        /// Save this run's medians as a baseline, keeping other days' entries
+        #[arg(long)]
+        save_baseline: Option<PathBuf>,
+    },
+}
+
+/// Days with a solution, in order.
+const DAYS: std::ops::RangeInclusive<u8> = 1..=11;
+
+fn main() -> miette::Result<()> {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Run { day, part, input } => {
+            check_day(day)?;
+            let path = input.unwrap_or_else(|| default_input_path(day));
+            let input = read_input_from(&path.to_string_lossy())?;
+
+            let parts = match part {
+                Some(1) => vec![Part::One],
+                Some(2) => vec![Part::Two],
+                _ => Part::ALL.to_vec(),
+            };
+            for part in parts {
+                let answer = solve_day(day, part, &input)?;
+                print_answer(day, part, &answer);
+            }
+        }
+        Command::Verify { day, manifest } => {
+            let manifest = manifest.unwrap_or_else(default_manifest_path);
+            verify::verify(&manifest, day)?;
+        }
+        Command::Bench {
+            day,
+            samples,
+            examples,
+            baseline,
+            save_baseline,
+        } => {
+            let baseline = baseline.map(|p| bench::Baseline::load(&p)).transpose()?;
+            let mut to_save = save_baseline
+                .as_deref()
+                .map(bench::Baseline::load_or_default)
+                .transpose()?;
+
+            // When benchmarking everything, days without an input are skipped
+            // rather than failing the whole run.
+            let (days, skip_missing) = match day {
+                Some(day) => {
+                    check_day(day)?;
+                    (day..=day, false)
+                }
+                None => (DAYS, true),
+            };
+            // A day or part that fails is reported and the run goes on, so
+            // the other days' timings still get saved.
+            let mut failures = 0;
+            for day in days {
+                let path = if examples {
+                    day_file_path(day, "example.txt")
+                } else {
+                    default_input_path(day)
+                };
+                if skip_missing && !path.exists() {
+                    println!("Day {day}: skipped, no {}", path.display());
+                    continue;
+                }
+
+                let timings = read_input_from(&path.to_string_lossy())
+                    .map_err(miette::Report::from)
+                    .and_then(|input| bench_day(day, &input, samples as usize));
+                match timings {
+                    Ok(timings) => {
+                        bench::report(day, &timings, baseline.as_ref());
+                        failures += [&timings.part_one, &timings.part_two]
+                            .iter()
+                            .filter(|part| part.is_err())
+                            .count();
+                        if let Some(to_save) = &mut to_save {
+                            to_save.record(day, &timings);
+                        }
+                    }
+                    Err(e) => {
+                        println!("Day {day}: failed: {e}");
+                        failures += 1;
+                    }
+                }
+            }
+
+            if let (Some(to_save), Some(path)) = (to_save, save_baseline) {
+                to_save.save(&path)?;
+            }
+            if failures > 0 {
+                miette::bail!("{failures} of the benchmarks failed");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Fails for a day without a solution, before anything looks for its files.
+fn check_day(day: u8) -> miette::Result<()> {
+    if !DAYS.contains(&day) {
+        miette::bail!("no solution for day {day}");
+    }
+    Ok(())
+}
+
+fn default_input_path(day: u8) -> PathBuf {
+    day_file_path(day, "input.txt")
+}
+
+fn day_file_path(day: u8, file: &str) -> PathBuf {
+    [
+        env!("CARGO_MANIFEST_DIR"),
+        "..",
+        &format!("day{day}"),
+        "src",
+        file,
+    ]
+    .iter()
+    .collect()
+}
+
+fn default_manifest_path() -> PathBuf {
+    [env!("CARGO_MANIFEST_DIR"), "..", "answers.toml"]
+        .iter()
+        .collect()
+}
+
+fn solve_day(day: u8, part: Part, input: &str) -> miette::Result<String> {
+    match day {
+        day1::Day1::DAY => solve::<day1::Day1>(part, input),
+        day2::Day2::DAY => solve::<day2::Day2>(part, input),
+        day3::Day3::DAY => solve::<day3::Day3>(part, input),
+        day4::Day4::DAY => solve::<day4::Day4>(part, input),
+        day5::Day5::DAY => solve::<day5::Day5>(part, input),
+        day6::Day6::DAY => solve::<day6::Day6>(part, input),
+        day7::Day7::DAY => solve::<day7::Day7>(part, input),
+        day8::Day8::DAY => solve::<day8::Day8>(part, input),
+        day9::Day9::DAY => solve::<day9::Day9>(part, input),
+        day10::Day10::DAY => solve::<day10::Day10>(part, input),
+        day11::Day11::DAY => solve::<day11::Day11>(part, input),
+        _ => miette::bail!("no solution for day {day}"),
+    }
+}
+
+fn bench_day(day: u8, input: &str, samples: usize) -> miette::Result<Timings> {
+    match day {
+        day1::Day1::DAY => time_solution::<day1::Day1>(input, samples),
+        day2::Day2::DAY => time_solution::<day2::Day2>(input, samples),
+        day3::Day3::DAY => time_solution::<day3::Day3>(input, samples),
+        day4::Day4::DAY => time_solution::<day4::Day4>(input, samples),
+        day5::Day5::DAY => time_solution::<day5::Day5>(input, samples),
+        day6::Day6::DAY => time_solution::<day6::Day6>(input, samples),
+        day7::Day7::DAY => time_solution::<day7::Day7>(input, samples),
+        day8::Day8::DAY => time_solution::<day8::Day8>(input, samples),
+        day9::Day9::DAY => time_solution::<day9::Day9>(input, samples),
+        day10::Day10::DAY => time_solution::<day10::Day10>(input, samples),
+        day11::Day11::DAY => time_solution::<day11::Day11>(input, samples),
+        _ => miette::bail!("no solution for day {day}"),
+    }
+}
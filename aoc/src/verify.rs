@@ -0,0 +1,105 @@
+use std::path::{Path, PathBuf};
+
+use aoc_core::{input::read_input_from, solution::Part};
+use miette::IntoDiagnostic;
+use serde::Deserialize;
+
+use crate::solve_day;
+
+/// The expected-answers file: a list of inputs and what each part should
+/// produce for them.
+#[derive(Deserialize)]
+pub struct Manifest {
+    #[serde(rename = "check")]
+    checks: Vec<Check>,
+}
+
+#[derive(Deserialize)]
+struct Check {
+    day: u8,
+    /// Relative to the manifest's directory.
+    input: PathBuf,
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+impl Check {
+    fn expected(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> miette::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .into_diagnostic()
+            .map_err(|e| {
+                e.wrap_err(format!(
+                    "could not read answers manifest {}",
+                    path.display()
+                ))
+            })?;
+        toml::from_str(&text)
+            .into_diagnostic()
+            .map_err(|e| e.wrap_err(format!("invalid answers manifest {}", path.display())))
+    }
+}
+
+/// Runs every check in the manifest (or only those for `day`), printing a
+/// line per part and a diff for each mismatch. Fails if any check did.
+pub fn verify(manifest_path: &Path, day: Option<u8>) -> miette::Result<()> {
+    let manifest = Manifest::load(manifest_path)?;
+    let base = manifest_path.parent().unwrap_or(Path::new("."));
+
+    let (mut passed, mut failed) = (0, 0);
+    for check in manifest
+        .checks
+        .iter()
+        .filter(|c| day.is_none() || day == Some(c.day))
+    {
+        let input_path = base.join(&check.input);
+        let input = match read_input_from(&input_path.to_string_lossy()) {
+            Ok(input) => input,
+            Err(e) => {
+                println!("FAIL day {}: {e}", check.day);
+                failed += 1;
+                continue;
+            }
+        };
+
+        for part in Part::ALL {
+            let Some(expected) = check.expected(part) else {
+                continue;
+            };
+            let label = format!("day {}, part {part} ({})", check.day, check.input.display());
+
+            match solve_day(check.day, part, &input) {
+                Ok(answer) if answer == expected => {
+                    println!("PASS {label}");
+                    passed += 1;
+                }
+                Ok(answer) => {
+                    println!("FAIL {label}, expected < / got >");
+                    println!(
+                        "{}",
+                        pretty_assertions::StrComparison::new(expected, &answer)
+                    );
+                    failed += 1;
+                }
+                Err(e) => {
+                    println!("FAIL {label}: {e:?}");
+                    failed += 1;
+                }
+            }
+        }
+    }
+
+    println!("{passed} passed, {failed} failed");
+    if failed > 0 {
+        miette::bail!("{failed} check(s) did not match the expected answers");
+    }
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use aoc_core::{parse::try_parse_lines, solution::Solution};
+use itertools::Itertools;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    /// Calories carried by each elf.
+    type Input = Vec<u64>;
+    type AnswerOne = u64;
+    type AnswerTwo = u64;
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        // A blank line ends an elf's list.
+        let lines = try_parse_lines(input, |line| match line {
+            "" => Ok(None),
+            calories => calories.parse::<u64>().map(Some),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(lines
+            .into_iter()
+            .batching(|it| it.map_while(|x| x).sum1::<u64>())
+            .collect())
+    }
+
+    fn part_one(elves: &Self::Input) -> miette::Result<u64> {
+        elves
+            .iter()
+            .copied()
+            .max()
+            .ok_or_else(|| miette::miette!("no elves in input"))
+    }
+
+    fn part_two(elves: &Self::Input) -> miette::Result<u64> {
+        let max = elves
+            .iter()
+            .copied()
+            .map(std::cmp::Reverse)
+            .k_smallest(3)
+            .map(|x| x.0)
+            .sum::<u64>();
+
+        Ok(max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part_one_example() {
+        let input = Day1::parse(EXAMPLE).unwrap();
+        assert_eq!(Day1::part_one(&input).unwrap(), 24000);
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day1::parse(EXAMPLE).unwrap();
+        assert_eq!(Day1::part_two(&input).unwrap(), 45000);
+    }
+}
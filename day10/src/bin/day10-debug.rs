@@ -0,0 +1,27 @@
+use std::{io, path::PathBuf};
+
+use aoc_core::{input::read_input_from, solution::Solution};
+use clap::Parser;
+use day10::{debugger::Debugger, Day10, MachineState};
+
+/// Step through a day 10 program, stopping at breakpoints; type `help` at the
+/// prompt for the commands
+#[derive(Parser)]
+struct Cli {
+    /// Program to debug; defaults to the bundled `src/input.txt`
+    input: Option<PathBuf>,
+}
+
+fn main() -> miette::Result<()> {
+    let cli = Cli::parse();
+    let path = cli
+        .input
+        .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt")));
+    let input = read_input_from(&path.to_string_lossy())?;
+
+    let program = Day10::parse(&input)?;
+    let mut debugger = Debugger::new(MachineState::new(&program));
+    debugger
+        .repl(io::stdin().lock(), io::stdout())
+        .map_err(|e| miette::miette!("couldn't talk to the terminal: {e}"))
+}
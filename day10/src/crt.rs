@@ -0,0 +1,164 @@
+use std::fmt;
+
+/// A screen drawn one pixel per cycle, left to right and top to bottom. A
+/// pixel is lit if the sprite covers its column when it's drawn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crt {
+    width: usize,
+    height: usize,
+    sprite_width: usize,
+    /// One bit per pixel, row after row.
+    pixels: Vec<u64>,
+}
+
+impl Default for Crt {
+    /// The puzzle's: 40 by 6, with a sprite 3 pixels wide.
+    fn default() -> Self {
+        Self::new(40, 6, 3)
+    }
+}
+
+impl Crt {
+    /// A sprite at `x` covers the columns from `x - (sprite_width - 1) / 2`
+    /// to `x + sprite_width / 2`. Panics if any size is zero.
+    pub fn new(width: usize, height: usize, sprite_width: usize) -> Self {
+        assert!(
+            width > 0 && height > 0 && sprite_width > 0,
+            "CRT sizes must be positive"
+        );
+        Self {
+            width,
+            height,
+            sprite_width,
+            pixels: vec![0; (width * height).div_ceil(64)],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn sprite_width(&self) -> usize {
+        self.sprite_width
+    }
+
+    /// Whether a sprite at `sprite_x` covers column `col`.
+    pub fn sprite_covers(&self, sprite_x: i32, col: usize) -> bool {
+        let x = i64::from(sprite_x);
+        let col = col as i64;
+        let left = x - (self.sprite_width as i64 - 1) / 2;
+        let right = x + self.sprite_width as i64 / 2;
+        (left..=right).contains(&col)
+    }
+
+    /// Draws the pixel the beam is on during `cycle` (counting from 0). The
+    /// beam goes back to the top left after the last pixel.
+    pub fn draw(&mut self, cycle: u32, sprite_x: i32) {
+        let pixel = cycle as usize % (self.width * self.height);
+        let lit = self.sprite_covers(sprite_x, pixel % self.width);
+        let (word, bit) = (pixel / 64, pixel % 64);
+        if lit {
+            self.pixels[word] |= 1 << bit;
+        } else {
+            self.pixels[word] &= !(1 << bit);
+        }
+    }
+
+    pub fn is_lit(&self, row: usize, col: usize) -> bool {
+        assert!(row < self.height && col < self.width, "no such pixel");
+        let pixel = row * self.width + col;
+        self.pixels[pixel / 64] & (1 << (pixel % 64)) != 0
+    }
+}
+
+/// Draws lit pixels as `#` and dark ones as `.`, with no final newline.
+impl fmt::Display for Crt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.height {
+            if row > 0 {
+                writeln!(f)?;
+            }
+            for col in 0..self.width {
+                let c = if self.is_lit(row, col) { '#' } else { '.' };
+                write!(f, "{c}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// The columns a sprite at `x` covers, as a row of the screen.
+    fn sprite_row(crt: &Crt, x: i32) -> String {
+        (0..crt.width())
+            .map(|col| if crt.sprite_covers(x, col) { '1' } else { '0' })
+            .collect()
+    }
+
+    #[test]
+    fn sprite_at_the_edges() {
+        for width in [1, 3, 40, 64, 65, 100, 130] {
+            let crt = Crt::new(width, 1, 3);
+            let w = width as i32;
+            let row = |lit: &[i32]| {
+                (0..w)
+                    .map(|col| if lit.contains(&col) { '1' } else { '0' })
+                    .collect::<String>()
+            };
+
+            assert_eq!(sprite_row(&crt, -2), row(&[]), "width {width}");
+            assert_eq!(sprite_row(&crt, -1), row(&[0]), "width {width}");
+            assert_eq!(sprite_row(&crt, 0), row(&[0, 1]), "width {width}");
+            assert_eq!(sprite_row(&crt, 1), row(&[0, 1, 2]), "width {width}");
+            assert_eq!(sprite_row(&crt, w - 2), row(&[w - 3, w - 2, w - 1]));
+            assert_eq!(sprite_row(&crt, w - 1), row(&[w - 2, w - 1]));
+            assert_eq!(sprite_row(&crt, w), row(&[w - 1]));
+            assert_eq!(sprite_row(&crt, w + 1), row(&[]));
+        }
+    }
+
+    #[test]
+    fn wider_sprites() {
+        let crt = Crt::new(10, 1, 5);
+        assert_eq!(sprite_row(&crt, 4), "0011111000");
+        let crt = Crt::new(10, 1, 4);
+        assert_eq!(sprite_row(&crt, 4), "0001111000");
+        let crt = Crt::new(10, 1, 1);
+        assert_eq!(sprite_row(&crt, 4), "0000100000");
+    }
+
+    #[test]
+    fn draws_row_by_row_past_64_columns() {
+        for width in [40, 64, 100] {
+            let mut crt = Crt::new(width, 3, 3);
+            // The sprite follows the beam on the middle row only.
+            for cycle in 0..3 * width as u32 {
+                let col = cycle as i32 % width as i32;
+                let sprite_x = if cycle as usize / width == 1 { col } else { -5 };
+                crt.draw(cycle, sprite_x);
+            }
+
+            let dark = ".".repeat(width);
+            let lit = "#".repeat(width);
+            assert_eq!(crt.to_string(), format!("{dark}\n{lit}\n{dark}"));
+        }
+    }
+
+    #[test]
+    fn beam_wraps_around() {
+        let mut crt = Crt::new(2, 1, 1);
+        crt.draw(0, 0);
+        crt.draw(1, 0);
+        assert_eq!(crt.to_string(), "#.");
+        crt.draw(2, 5);
+        assert_eq!(crt.to_string(), "..");
+    }
+}
@@ -0,0 +1,524 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    io::{self, BufRead, Write},
+};
+
+use aoc_core::parse::{parse_all, parse_number, BadInput, ParseResult};
+use nom::{
+    branch::alt,
+    character::complete::{i32, satisfy, space0, space1, u32},
+    combinator::{map, not, opt, value},
+    sequence::{pair, preceded, terminated, tuple},
+};
+use nom_supreme::{error::ErrorTree, tag::complete::tag};
+
+use crate::{isa::Register, machine::MachineState};
+
+/// How a register is compared with a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Comparison {
+    pub fn holds(self, left: i32, right: i32) -> bool {
+        match self {
+            Comparison::Eq => left == right,
+            Comparison::Ne => left != right,
+            Comparison::Lt => left < right,
+            Comparison::Le => left <= right,
+            Comparison::Gt => left > right,
+            Comparison::Ge => left >= right,
+        }
+    }
+
+    fn parse(i: &str) -> ParseResult<'_, Self> {
+        alt((
+            value(Comparison::Eq, tag("==")),
+            value(Comparison::Ne, tag("!=")),
+            value(Comparison::Le, tag("<=")),
+            value(Comparison::Ge, tag(">=")),
+            value(Comparison::Lt, tag("<")),
+            value(Comparison::Gt, tag(">")),
+        ))(i)
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = match self {
+            Comparison::Eq => "==",
+            Comparison::Ne => "!=",
+            Comparison::Lt => "<",
+            Comparison::Le => "<=",
+            Comparison::Gt => ">",
+            Comparison::Ge => ">=",
+        };
+        write!(f, "{op}")
+    }
+}
+
+/// Where the debugger stops. It checks between cycles, so the registers it
+/// shows are the ones during the cycle about to run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// Before cycle `n` (counting from 1) runs.
+    Cycle(u32),
+    /// Before the instruction at this index starts.
+    Pc(usize),
+    /// Once the register comes to compare this way with the value. It has
+    /// to stop holding before it stops the machine again.
+    When(Register, Comparison, i32),
+}
+
+impl Breakpoint {
+    fn parse(i: &str) -> ParseResult<'_, Self> {
+        alt((
+            map(
+                preceded(pair(keyword(&["cycle"]), space1), u32),
+                Breakpoint::Cycle,
+            ),
+            map(
+                preceded(pair(keyword(&["pc"]), space1), parse_number),
+                Breakpoint::Pc,
+            ),
+            map(
+                tuple((
+                    terminated(Register::parse, space0),
+                    terminated(Comparison::parse, space0),
+                    i32,
+                )),
+                |(r, cmp, v)| Breakpoint::When(r, cmp, v),
+            ),
+        ))(i)
+    }
+
+    /// Whether this would stop `ms` now, `held` being whether a condition
+    /// held before the last cycle.
+    fn hits(&self, ms: &MachineState, held: bool) -> bool {
+        match *self {
+            Breakpoint::Cycle(n) => ms.cycle() + 1 == n,
+            Breakpoint::Pc(index) => {
+                ms.pc() == index && ms.current().is_some_and(|(ins, left)| left == ins.cycles())
+            }
+            Breakpoint::When(..) => !held && self.condition_holds(ms),
+        }
+    }
+
+    fn condition_holds(&self, ms: &MachineState) -> bool {
+        match *self {
+            Breakpoint::When(r, cmp, v) => cmp.holds(ms.register(r), v),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Breakpoint::Cycle(n) => write!(f, "cycle {n}"),
+            Breakpoint::Pc(index) => write!(f, "pc {index}"),
+            Breakpoint::When(r, cmp, v) => write!(f, "{r} {cmp} {v}"),
+        }
+    }
+}
+
+/// What can be typed at the debugger's prompt; see [`HELP`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Step(u32),
+    Continue,
+    Break(Breakpoint),
+    Delete(usize),
+    Breakpoints,
+    Registers,
+    Screen,
+    Help,
+    Quit,
+}
+
+pub const HELP: &str = "\
+step [n], s [n]       run n cycles (1 by default), stopping at breakpoints
+continue, c           run until a breakpoint or the end of the program
+break <where>, b      add a breakpoint, <where> being one of
+                        cycle <n>    before cycle n runs
+                        pc <i>       before instruction i starts
+                        <r> <op> <v> once register r comes to compare with v,
+                                     <op> being ==, !=, <, <=, > or >=
+delete <id>, d <id>   remove a breakpoint
+breakpoints, info     list breakpoints
+registers, r          show the registers
+screen, crt           show the CRT
+help, h               show this
+quit, q               leave
+An empty line repeats the last command.";
+
+impl Command {
+    pub fn parse(line: &str) -> Result<Self, BadInput> {
+        parse_all(line, Self::parse_command)
+    }
+
+    fn parse_command(i: &str) -> ParseResult<'_, Self> {
+        alt((
+            map(
+                preceded(keyword(&["step", "s"]), opt(preceded(space1, u32))),
+                |n| Command::Step(n.unwrap_or(1)),
+            ),
+            value(Command::Continue, keyword(&["continue", "c"])),
+            map(
+                preceded(pair(keyword(&["break", "b"]), space1), Breakpoint::parse),
+                Command::Break,
+            ),
+            map(
+                preceded(pair(keyword(&["delete", "d"]), space1), parse_number),
+                Command::Delete,
+            ),
+            value(Command::Breakpoints, keyword(&["breakpoints", "info"])),
+            value(Command::Registers, keyword(&["registers", "r"])),
+            value(Command::Screen, keyword(&["screen", "crt"])),
+            value(Command::Help, keyword(&["help", "h"])),
+            value(Command::Quit, keyword(&["quit", "q"])),
+        ))(i)
+    }
+}
+
+/// Matches any of `names` as a whole word.
+fn keyword<'a>(names: &'static [&'static str]) -> impl FnMut(&'a str) -> ParseResult<'a, &'a str> {
+    move |i| {
+        let mut alternatives = vec![];
+        for name in names {
+            match terminated(tag(*name), not(satisfy(|c| c.is_ascii_alphanumeric())))(i) {
+                Ok(res) => return Ok(res),
+                Err(nom::Err::Error(e)) => alternatives.push(e),
+                Err(e) => return Err(e),
+            }
+        }
+        Err(nom::Err::Error(ErrorTree::Alt(alternatives)))
+    }
+}
+
+/// Why the debugger gave control back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// It ran the cycles it was asked to.
+    Stepped,
+    /// The breakpoint with this id was hit.
+    Breakpoint(usize),
+    Halted,
+}
+
+/// Runs a machine a cycle at a time, drawing on its CRT as it goes, until it
+/// hits a breakpoint.
+pub struct Debugger {
+    ms: MachineState,
+    breakpoints: BTreeMap<usize, Breakpoint>,
+    next_id: usize,
+}
+
+impl Debugger {
+    pub fn new(ms: MachineState) -> Self {
+        Self {
+            ms,
+            breakpoints: BTreeMap::new(),
+            next_id: 1,
+        }
+    }
+
+    pub fn machine(&self) -> &MachineState {
+        &self.ms
+    }
+
+    /// Returns the new breakpoint's id.
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.breakpoints.insert(id, breakpoint);
+        id
+    }
+
+    /// Returns whether there was a breakpoint with that id.
+    pub fn delete_breakpoint(&mut self, id: usize) -> bool {
+        self.breakpoints.remove(&id).is_some()
+    }
+
+    pub fn breakpoints(&self) -> impl Iterator<Item = (usize, &Breakpoint)> {
+        self.breakpoints.iter().map(|(&id, bp)| (id, bp))
+    }
+
+    /// Runs up to `cycles` cycles. Stopping at a breakpoint takes at least
+    /// one, so stepping off one works.
+    pub fn step(&mut self, cycles: u32) -> Stop {
+        for _ in 0..cycles {
+            if let Some(stop) = self.run_cycle() {
+                return stop;
+            }
+        }
+        Stop::Stepped
+    }
+
+    /// Runs until a breakpoint is hit or the program ends.
+    pub fn resume(&mut self) -> Stop {
+        loop {
+            if let Some(stop) = self.run_cycle() {
+                return stop;
+            }
+        }
+    }
+
+    fn run_cycle(&mut self) -> Option<Stop> {
+        if self.ms.is_halted() {
+            return Some(Stop::Halted);
+        }
+
+        let held = self
+            .breakpoints
+            .values()
+            .map(|bp| bp.condition_holds(&self.ms))
+            .collect::<Vec<_>>();
+        self.ms.draw();
+        self.ms.step();
+
+        self.breakpoints
+            .iter()
+            .zip(held)
+            .find(|((_, bp), held)| bp.hits(&self.ms, *held))
+            .map(|((&id, _), _)| Stop::Breakpoint(id))
+            .or_else(|| self.ms.is_halted().then_some(Stop::Halted))
+    }
+
+    /// Where the machine is, on one line.
+    pub fn status(&self) -> String {
+        let ms = &self.ms;
+        match ms.current() {
+            Some((ins, left)) => format!(
+                "cycle {}, pc {}: {ins} ({left} of {} cycles left), {}",
+                ms.cycle() + 1,
+                ms.pc(),
+                ins.cycles(),
+                self.registers(),
+            ),
+            None => format!("halted after cycle {}, {}", ms.cycle(), self.registers()),
+        }
+    }
+
+    fn registers(&self) -> String {
+        Register::ALL
+            .iter()
+            .map(|&r| format!("{r}={}", self.ms.register(r)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Carries out `command`, writing what it did to `out`. Returns `false`
+    /// for [`Command::Quit`].
+    pub fn execute(&mut self, command: Command, out: &mut impl Write) -> io::Result<bool> {
+        match command {
+            Command::Step(cycles) => {
+                let stop = self.step(cycles);
+                self.report(stop, out)?;
+            }
+            Command::Continue => {
+                let stop = self.resume();
+                self.report(stop, out)?;
+            }
+            Command::Break(bp) => {
+                let id = self.add_breakpoint(bp);
+                writeln!(out, "breakpoint {id}: {bp}")?;
+            }
+            Command::Delete(id) => {
+                if self.delete_breakpoint(id) {
+                    writeln!(out, "deleted breakpoint {id}")?;
+                } else {
+                    writeln!(out, "there's no breakpoint {id}")?;
+                }
+            }
+            Command::Breakpoints => {
+                if self.breakpoints.is_empty() {
+                    writeln!(out, "no breakpoints")?;
+                }
+                for (id, bp) in self.breakpoints() {
+                    writeln!(out, "breakpoint {id}: {bp}")?;
+                }
+            }
+            Command::Registers => writeln!(out, "{}", self.registers())?,
+            Command::Screen => writeln!(out, "{}", self.ms.render_display())?,
+            Command::Help => writeln!(out, "{HELP}")?,
+            Command::Quit => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn report(&self, stop: Stop, out: &mut impl Write) -> io::Result<()> {
+        match stop {
+            Stop::Stepped => {}
+            Stop::Breakpoint(id) => {
+                writeln!(out, "hit breakpoint {id}: {}", self.breakpoints[&id])?
+            }
+            Stop::Halted => writeln!(out, "the program has ended")?,
+        }
+        writeln!(out, "{}", self.status())
+    }
+
+    /// Reads commands from `input` until it ends or says to quit, prompting
+    /// and answering on `out`.
+    pub fn repl(&mut self, input: impl BufRead, mut out: impl Write) -> io::Result<()> {
+        const PROMPT: &str = "(day10) ";
+
+        writeln!(out, "{}", self.status())?;
+        write!(out, "{PROMPT}")?;
+        out.flush()?;
+
+        let mut last = None;
+        for line in input.lines() {
+            let line = line?;
+            let command = if line.trim().is_empty() {
+                last
+            } else {
+                match Command::parse(line.trim()) {
+                    Ok(command) => Some(command),
+                    Err(e) => {
+                        writeln!(out, "{:?}", miette::Report::new(e))?;
+                        None
+                    }
+                }
+            };
+
+            if let Some(command) = command {
+                if !self.execute(command, &mut out)? {
+                    return Ok(());
+                }
+                last = Some(command);
+            }
+            write!(out, "{PROMPT}")?;
+            out.flush()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::isa::Program;
+    use pretty_assertions::assert_eq;
+
+    fn debugger(source: &str) -> Debugger {
+        Debugger::new(MachineState::new(&Program::parse(source).unwrap()))
+    }
+
+    #[test]
+    fn commands() {
+        let parse = |line| Command::parse(line).unwrap();
+        assert_eq!(parse("s"), Command::Step(1));
+        assert_eq!(parse("step 5"), Command::Step(5));
+        assert_eq!(parse("c"), Command::Continue);
+        assert_eq!(parse("b cycle 20"), Command::Break(Breakpoint::Cycle(20)));
+        assert_eq!(parse("break pc 3"), Command::Break(Breakpoint::Pc(3)));
+        assert_eq!(
+            parse("b x<=-2"),
+            Command::Break(Breakpoint::When(Register::X, Comparison::Le, -2))
+        );
+        assert_eq!(
+            parse("break y != 0"),
+            Command::Break(Breakpoint::When(Register::Y, Comparison::Ne, 0))
+        );
+        assert_eq!(parse("screen"), Command::Screen);
+        assert_eq!(parse("d 2"), Command::Delete(2));
+        assert_eq!(parse("q"), Command::Quit);
+
+        for bad in ["stepx", "step -1", "break", "b w > 1", "b cycle"] {
+            assert!(Command::parse(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn break_on_cycle() {
+        let mut dbg = debugger(include_str!("example.txt"));
+        let id = dbg.add_breakpoint(Breakpoint::Cycle(20));
+
+        assert_eq!(dbg.resume(), Stop::Breakpoint(id));
+        assert_eq!(dbg.machine().cycle() + 1, 20);
+        assert_eq!(dbg.machine().x(), 21);
+        assert_eq!(dbg.resume(), Stop::Halted);
+        assert_eq!(dbg.machine().cycle(), 240);
+        assert_eq!(dbg.step(1), Stop::Halted);
+    }
+
+    #[test]
+    fn break_on_instruction() {
+        let mut dbg = debugger("noop\naddx 3\naddx -5");
+        dbg.add_breakpoint(Breakpoint::Pc(2));
+
+        assert_eq!(dbg.resume(), Stop::Breakpoint(1));
+        assert_eq!(dbg.machine().cycle(), 3);
+        assert_eq!(dbg.machine().x(), 4);
+        assert_eq!(
+            dbg.status(),
+            "cycle 4, pc 2: addx -5 (2 of 2 cycles left), x=4 y=0 z=0"
+        );
+        // Not again halfway through the instruction.
+        assert_eq!(dbg.resume(), Stop::Halted);
+    }
+
+    #[test]
+    fn break_when_a_condition_starts_holding() {
+        let mut dbg = debugger("addx 5\naddx 1\naddx -5\naddx 5");
+        dbg.add_breakpoint(Breakpoint::When(Register::X, Comparison::Ge, 6));
+
+        assert_eq!(dbg.resume(), Stop::Breakpoint(1));
+        assert_eq!(dbg.machine().cycle(), 2);
+        assert_eq!(dbg.resume(), Stop::Breakpoint(1));
+        assert_eq!(dbg.machine().cycle(), 8);
+        assert_eq!(dbg.resume(), Stop::Halted);
+    }
+
+    #[test]
+    fn stepping_stops_at_breakpoints() {
+        let mut dbg = debugger(include_str!("example.txt"));
+        dbg.add_breakpoint(Breakpoint::Cycle(5));
+
+        assert_eq!(dbg.step(3), Stop::Stepped);
+        assert_eq!(dbg.step(3), Stop::Breakpoint(1));
+        assert_eq!(dbg.machine().cycle(), 4);
+        assert_eq!(dbg.step(3), Stop::Stepped);
+        assert_eq!(dbg.machine().cycle(), 7);
+    }
+
+    #[test]
+    fn session() {
+        let mut dbg = debugger("noop\naddx 3\naddx -5");
+        let script = "b cycle 3\nbogus\nc\n\nregisters\nbreakpoints\nd 1\nd 1\nquit\ns\n";
+        let mut out = vec![];
+        dbg.repl(script.as_bytes(), &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        let lines = out.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[0],
+            "cycle 1, pc 0: noop (1 of 1 cycles left), x=1 y=0 z=0"
+        );
+        assert_eq!(lines[1], "(day10) breakpoint 1: cycle 3");
+        assert!(out.contains("bad input"), "{out}");
+
+        let tail = &out[out.find("hit breakpoint").unwrap()..];
+        assert_eq!(
+            tail,
+            "\
+hit breakpoint 1: cycle 3
+cycle 3, pc 1: addx 3 (1 of 2 cycles left), x=1 y=0 z=0
+(day10) the program has ended
+halted after cycle 5, x=-1 y=0 z=0
+(day10) x=-1 y=0 z=0
+(day10) breakpoint 1: cycle 3
+(day10) deleted breakpoint 1
+(day10) there's no breakpoint 1
+(day10) "
+        );
+    }
+}
@@ -0,0 +1,424 @@
+use std::{collections::BTreeMap, fmt};
+
+use aoc_core::parse::{parse_lines, BadInput, ParseResult};
+use nom::{
+    branch::alt,
+    bytes::complete::take_while,
+    character::complete::{char, satisfy, space0, space1},
+    combinator::{consumed, map, opt, recognize, value},
+    sequence::{pair, preceded, terminated},
+};
+use nom_supreme::{error::ErrorTree, tag::complete::tag};
+
+/// A register. The CRT's sprite follows `x`, the only one the puzzle has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Register {
+    X,
+    Y,
+    Z,
+}
+
+impl Register {
+    pub const ALL: [Register; 3] = [Register::X, Register::Y, Register::Z];
+
+    pub(crate) fn index(self) -> usize {
+        self as usize
+    }
+
+    pub(crate) fn parse(i: &str) -> ParseResult<'_, Self> {
+        alt((
+            value(Register::X, char('x')),
+            value(Register::Y, char('y')),
+            value(Register::Z, char('z')),
+        ))(i)
+    }
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Register::X => 'x',
+            Register::Y => 'y',
+            Register::Z => 'z',
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// What an instruction works with: a constant, or what's in a register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operand {
+    Value(i32),
+    Register(Register),
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Value(v) => write!(f, "{v}"),
+            Operand::Register(r) => write!(f, "{r}"),
+        }
+    }
+}
+
+impl Operand {
+    fn parse(i: &str) -> ParseResult<'_, Self> {
+        alt((
+            map(nom::character::complete::i32, Operand::Value),
+            map(Register::parse, Operand::Register),
+        ))(i)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Opcode {
+    Noop,
+    Add,
+    Mul,
+    Jmp,
+    Jz,
+    Jnz,
+}
+
+#[derive(Debug)]
+pub struct OpcodeInfo {
+    pub opcode: Opcode,
+    pub mnemonic: &'static str,
+    /// Cycles the instruction takes; its effect happens at the end of the
+    /// last one.
+    pub cycles: u32,
+}
+
+/// How every opcode is written and how long it takes.
+///
+/// - `noop` does nothing.
+/// - `add<r> <operand>` and `mul<r> <operand>` (`addx 3`, `muly z`) add to or
+///   multiply register `r`, wrapping on overflow.
+/// - `jmp <target>` jumps; `jz <r> <target>` and `jnz <r> <target>` jump if
+///   register `r` is or isn't zero. A target is a label or a signed offset
+///   from the jump itself.
+pub const OPCODES: [OpcodeInfo; 6] = [
+    OpcodeInfo {
+        opcode: Opcode::Noop,
+        mnemonic: "noop",
+        cycles: 1,
+    },
+    OpcodeInfo {
+        opcode: Opcode::Add,
+        mnemonic: "add",
+        cycles: 2,
+    },
+    OpcodeInfo {
+        opcode: Opcode::Mul,
+        mnemonic: "mul",
+        cycles: 3,
+    },
+    OpcodeInfo {
+        opcode: Opcode::Jmp,
+        mnemonic: "jmp",
+        cycles: 1,
+    },
+    OpcodeInfo {
+        opcode: Opcode::Jz,
+        mnemonic: "jz",
+        cycles: 2,
+    },
+    OpcodeInfo {
+        opcode: Opcode::Jnz,
+        mnemonic: "jnz",
+        cycles: 2,
+    },
+];
+
+impl Opcode {
+    pub fn info(self) -> &'static OpcodeInfo {
+        OPCODES
+            .iter()
+            .find(|info| info.opcode == self)
+            .expect("every opcode is in OPCODES")
+    }
+
+    pub fn cycles(self) -> u32 {
+        self.info().cycles
+    }
+
+    fn parse(i: &str) -> ParseResult<'_, Self> {
+        let mut alternatives = vec![];
+        for info in &OPCODES {
+            match tag(info.mnemonic)(i) {
+                Ok((rest, _)) => return Ok((rest, info.opcode)),
+                Err(nom::Err::Error(e)) => alternatives.push(e),
+                Err(e) => return Err(e),
+            }
+        }
+        Err(nom::Err::Error(ErrorTree::Alt(alternatives)))
+    }
+}
+
+/// An instruction. Jump targets are `T`, which is an index into the program
+/// once it's been assembled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Instruction<T = usize> {
+    Noop,
+    Add(Register, Operand),
+    Mul(Register, Operand),
+    Jmp(T),
+    Jz(Register, T),
+    Jnz(Register, T),
+}
+
+impl<T> Instruction<T> {
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Instruction::Noop => Opcode::Noop,
+            Instruction::Add(..) => Opcode::Add,
+            Instruction::Mul(..) => Opcode::Mul,
+            Instruction::Jmp(_) => Opcode::Jmp,
+            Instruction::Jz(..) => Opcode::Jz,
+            Instruction::Jnz(..) => Opcode::Jnz,
+        }
+    }
+
+    pub fn cycles(&self) -> u32 {
+        self.opcode().cycles()
+    }
+
+    fn try_map_target<U, E>(self, f: impl FnOnce(T) -> Result<U, E>) -> Result<Instruction<U>, E> {
+        Ok(match self {
+            Instruction::Noop => Instruction::Noop,
+            Instruction::Add(r, o) => Instruction::Add(r, o),
+            Instruction::Mul(r, o) => Instruction::Mul(r, o),
+            Instruction::Jmp(t) => Instruction::Jmp(f(t)?),
+            Instruction::Jz(r, t) => Instruction::Jz(r, f(t)?),
+            Instruction::Jnz(r, t) => Instruction::Jnz(r, f(t)?),
+        })
+    }
+}
+
+/// Written the way it's parsed, except that jump targets are shown as the
+/// index they go to, `#4`.
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mnemonic = self.opcode().info().mnemonic;
+        match self {
+            Instruction::Noop => write!(f, "{mnemonic}"),
+            Instruction::Add(r, o) | Instruction::Mul(r, o) => write!(f, "{mnemonic}{r} {o}"),
+            Instruction::Jmp(to) => write!(f, "{mnemonic} #{to}"),
+            Instruction::Jz(r, to) | Instruction::Jnz(r, to) => write!(f, "{mnemonic} {r} #{to}"),
+        }
+    }
+}
+
+impl<'a> Instruction<Target<'a>> {
+    fn parse(i: &'a str) -> ParseResult<'a, Self> {
+        let (i, opcode) = Opcode::parse(i)?;
+        let register_and_operand = || pair(Register::parse, preceded(space1, Operand::parse));
+        let register_and_target = || {
+            pair(
+                preceded(space1, Register::parse),
+                preceded(space1, Target::parse),
+            )
+        };
+
+        match opcode {
+            Opcode::Noop => Ok((i, Instruction::Noop)),
+            Opcode::Add => map(register_and_operand(), |(r, o)| Instruction::Add(r, o))(i),
+            Opcode::Mul => map(register_and_operand(), |(r, o)| Instruction::Mul(r, o))(i),
+            Opcode::Jmp => map(preceded(space1, Target::parse), Instruction::Jmp)(i),
+            Opcode::Jz => map(register_and_target(), |(r, t)| Instruction::Jz(r, t))(i),
+            Opcode::Jnz => map(register_and_target(), |(r, t)| Instruction::Jnz(r, t))(i),
+        }
+    }
+}
+
+/// Where a jump goes, as written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Target<'a> {
+    Label(&'a str),
+    /// The offset, and how it was written.
+    Offset(i32, &'a str),
+}
+
+impl<'a> Target<'a> {
+    fn parse(i: &'a str) -> ParseResult<'a, Self> {
+        alt((
+            map(consumed(nom::character::complete::i32), |(text, n)| {
+                Target::Offset(n, text)
+            }),
+            map(label, Target::Label),
+        ))(i)
+    }
+}
+
+fn label(i: &str) -> ParseResult<'_, &str> {
+    recognize(pair(
+        satisfy(|c| c.is_ascii_alphabetic() || c == '_'),
+        take_while(|c: char| c.is_ascii_alphanumeric() || c == '_'),
+    ))(i)
+}
+
+enum Line<'a> {
+    Label(&'a str),
+    Instruction(Instruction<Target<'a>>),
+}
+
+/// A line of a program: a label (`loop:`), an instruction, or nothing, any
+/// of them indented and followed by a `;` comment.
+fn parse_line(i: &str) -> ParseResult<'_, Option<Line<'_>>> {
+    let (i, _) = space0(i)?;
+    if i.is_empty() || i.starts_with(';') {
+        return Ok(("", None));
+    }
+
+    let comment = pair(char(';'), take_while(|_| true));
+    terminated(
+        map(
+            alt((
+                map(terminated(label, char(':')), Line::Label),
+                map(Instruction::parse, Line::Instruction),
+            )),
+            Some,
+        ),
+        pair(space0, opt(comment)),
+    )(i)
+}
+
+/// Instructions ready to run, with jumps resolved.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Program {
+    pub instructions: Vec<Instruction>,
+    /// Where each label points.
+    pub labels: BTreeMap<String, usize>,
+}
+
+impl Program {
+    /// Parses and assembles a program, one instruction or label per line.
+    pub fn parse(input: &str) -> Result<Self, BadInput> {
+        let lines = parse_lines(input, parse_line).collect::<Result<Vec<_>, _>>()?;
+
+        let mut labels = BTreeMap::new();
+        let mut instructions = vec![];
+        for line in lines.iter().flatten() {
+            match line {
+                Line::Label(name) => {
+                    if labels
+                        .insert(name.to_string(), instructions.len())
+                        .is_some()
+                    {
+                        return Err(BadInput::at(input, name, "label defined again"));
+                    }
+                }
+                Line::Instruction(ins) => instructions.push(*ins),
+            }
+        }
+
+        let len = instructions.len();
+        let instructions = instructions
+            .into_iter()
+            .enumerate()
+            .map(|(index, ins)| {
+                ins.try_map_target(|target| match target {
+                    Target::Label(name) => labels
+                        .get(name)
+                        .copied()
+                        .ok_or_else(|| BadInput::at(input, name, "no such label")),
+                    // Jumping to just past the end halts the machine.
+                    Target::Offset(offset, text) => index
+                        .checked_add_signed(offset as isize)
+                        .filter(|&to| to <= len)
+                        .ok_or_else(|| BadInput::at(input, text, "jumps outside the program")),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            instructions,
+            labels,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(input: &str) -> Vec<String> {
+        let err = Program::parse(input).unwrap_err();
+        miette::Diagnostic::labels(&err)
+            .unwrap()
+            .filter_map(|l| l.label().map(str::to_owned))
+            .collect()
+    }
+
+    #[test]
+    fn every_opcode_is_in_the_table_once() {
+        for opcode in [
+            Opcode::Noop,
+            Opcode::Add,
+            Opcode::Mul,
+            Opcode::Jmp,
+            Opcode::Jz,
+            Opcode::Jnz,
+        ] {
+            let entries = OPCODES.iter().filter(|i| i.opcode == opcode).count();
+            assert_eq!(entries, 1, "{opcode:?}");
+        }
+    }
+
+    #[test]
+    fn instructions() {
+        let program = Program::parse(
+            "\
+noop
+addx -3
+addy x
+mulz 4
+start:  ; the loop
+  jmp start
+jz y +1
+jnz x -5",
+        )
+        .unwrap();
+
+        use Instruction::*;
+        assert_eq!(
+            program.instructions,
+            [
+                Noop,
+                Add(Register::X, Operand::Value(-3)),
+                Add(Register::Y, Operand::Register(Register::X)),
+                Mul(Register::Z, Operand::Value(4)),
+                Jmp(4),
+                Jz(Register::Y, 6),
+                Jnz(Register::X, 1),
+            ]
+        );
+        assert_eq!(program.labels["start"], 4);
+
+        let printed = program
+            .instructions
+            .iter()
+            .map(Instruction::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            printed,
+            ["noop", "addx -3", "addy x", "mulz 4", "jmp #4", "jz y #6", "jnz x #1"]
+        );
+    }
+
+    #[test]
+    fn unknown_opcode_lists_every_mnemonic() {
+        let labels = labels("nop");
+        for info in &OPCODES {
+            let expected = format!("expected {:?}", info.mnemonic);
+            assert!(labels.contains(&expected), "{expected} not in {labels:?}");
+        }
+    }
+
+    #[test]
+    fn bad_labels() {
+        assert_eq!(labels("jmp nowhere"), ["no such label"]);
+        assert_eq!(labels("a:\nnoop\na:\nnoop"), ["label defined again"]);
+        assert_eq!(labels("noop\njmp -2"), ["jumps outside the program"]);
+    }
+}
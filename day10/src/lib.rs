@@ -0,0 +1,109 @@
+use aoc_core::solution::Solution;
+
+pub mod crt;
+pub use crt::Crt;
+
+pub mod debugger;
+
+pub mod isa;
+pub use isa::{Instruction, Opcode, Operand, Program, Register, OPCODES};
+
+mod machine;
+pub use machine::MachineState;
+
+pub mod ocr;
+pub use ocr::{read_letters, OcrError};
+
+pub mod probe;
+pub use probe::{Probe, ProbeTrace, Sample};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Program;
+    type AnswerOne = i64;
+    type AnswerTwo = String;
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        Ok(Program::parse(input)?)
+    }
+
+    fn part_one(program: &Self::Input) -> miette::Result<i64> {
+        let mut ms = MachineState::new(program);
+        let trace = ms.run_probed(&Probe::at((20..=220).step_by(40)));
+        Ok(trace.signal_strength())
+    }
+
+    /// The letters on the screen. Fails on any that aren't in the font; see
+    /// [`draw_screen`] for the screen itself.
+    fn part_two(program: &Self::Input) -> miette::Result<String> {
+        Ok(read_letters(&draw_screen(program))?)
+    }
+}
+
+/// Runs `program` to the end, drawing on the puzzle's CRT. Stops after one
+/// frame, once every pixel has been drawn, if the program is still going.
+pub fn draw_screen(program: &Program) -> Crt {
+    let mut ms = MachineState::new(program);
+    let frame = (ms.crt().width() * ms.crt().height()) as u32;
+
+    while !ms.is_halted() && ms.cycle() < frame {
+        ms.draw();
+        ms.step();
+    }
+
+    ms.crt().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part_one_example() {
+        let input = Day10::parse(EXAMPLE).unwrap();
+        assert_eq!(Day10::part_one(&input).unwrap(), 13140);
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day10::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            draw_screen(&input).to_string(),
+            "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######....."
+        );
+
+        // Stripes aren't letters.
+        let err = read_letters(&draw_screen(&input)).unwrap_err();
+        assert!(matches!(err, OcrError::UnknownGlyph { position: 0, .. }));
+        assert!(Day10::part_two(&input).is_err());
+    }
+
+    #[test]
+    fn part_two_letters() {
+        let input = Day10::parse(include_str!("letters.txt")).unwrap();
+        assert_eq!(Day10::part_two(&input).unwrap(), "FGCUZREC");
+    }
+
+    #[test]
+    fn looping_programs_finish() {
+        let input = Day10::parse("loop:\n    noop\n    jz z loop").unwrap();
+        assert_eq!(
+            Day10::part_one(&input).unwrap(),
+            20 + 60 + 100 + 140 + 180 + 220
+        );
+        let row = format!("###{}", ".".repeat(37));
+        assert_eq!(draw_screen(&input).to_string(), [&row[..]; 6].join("\n"));
+    }
+}
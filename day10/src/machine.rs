@@ -0,0 +1,249 @@
+use std::fmt;
+
+use crate::{
+    crt::Crt,
+    isa::{Instruction, Operand, Program, Register},
+    probe::{Probe, ProbeTrace, Sample},
+};
+
+/// A CPU running a program a cycle at a time, with a CRT it can draw on.
+pub struct MachineState {
+    instructions: Vec<Instruction>,
+    /// Index of the instruction being executed, or of the next one.
+    pc: usize,
+    /// The instruction being executed and how many cycles it has left.
+    current: Option<(Instruction, u32)>,
+    /// Cycles completed so far.
+    cycle: u32,
+    registers: [i32; Register::ALL.len()],
+    crt: Crt,
+}
+
+impl fmt::Debug for MachineState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "cycle={} pc={} registers={:?} current={:?}",
+            self.cycle, self.pc, self.registers, self.current,
+        )?;
+        writeln!(f, "{}", self.render_display())
+    }
+}
+
+impl MachineState {
+    /// Starts with `x` at 1 and every other register at 0, and the puzzle's
+    /// CRT.
+    pub fn new(program: &Program) -> Self {
+        Self::with_crt(program, Crt::default())
+    }
+
+    pub fn with_crt(program: &Program, crt: Crt) -> Self {
+        let mut registers = [0; Register::ALL.len()];
+        registers[Register::X.index()] = 1;
+
+        let mut res = Self {
+            instructions: program.instructions.clone(),
+            pc: 0,
+            current: None,
+            cycle: 0,
+            registers,
+            crt,
+        };
+        res.decode();
+        res
+    }
+
+    pub fn cycle(&self) -> u32 {
+        self.cycle
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn register(&self, r: Register) -> i32 {
+        self.registers[r.index()]
+    }
+
+    pub fn x(&self) -> i32 {
+        self.register(Register::X)
+    }
+
+    /// The instruction under way and how many cycles it has left, counting
+    /// the one about to run.
+    pub fn current(&self) -> Option<(Instruction, u32)> {
+        self.current
+    }
+
+    /// Whether the program has run off its end.
+    pub fn is_halted(&self) -> bool {
+        self.current.is_none()
+    }
+
+    pub fn crt(&self) -> &Crt {
+        &self.crt
+    }
+
+    pub fn render_display(&self) -> String {
+        self.crt.to_string()
+    }
+
+    fn decode(&mut self) {
+        self.current = self
+            .instructions
+            .get(self.pc)
+            .map(|&ins| (ins, ins.cycles()));
+    }
+
+    /// Draws the pixel for the cycle under way, with the sprite at `x`.
+    pub fn draw(&mut self) {
+        self.crt.draw(self.cycle, self.x());
+    }
+
+    /// Runs one cycle. Returns `false`, doing nothing, once halted.
+    pub fn step(&mut self) -> bool {
+        let Some((ins, cycles_left)) = self.current.as_mut() else {
+            return false;
+        };
+
+        *cycles_left -= 1;
+        if *cycles_left == 0 {
+            let ins = *ins;
+            self.pc = self.execute(ins);
+            self.decode();
+        }
+
+        self.cycle += 1;
+        true
+    }
+
+    /// Runs until halted, sampling the registers during and after each cycle
+    /// `probe` picks. Stops early once [`Probe::last`] has been sampled, so a
+    /// program that loops forever can still be probed at given cycles.
+    pub fn run_probed(&mut self, probe: &Probe) -> ProbeTrace {
+        let mut trace = ProbeTrace::default();
+        let last = probe.last();
+        while !self.is_halted() && last.is_none_or(|last| self.cycle < last) {
+            let cycle = self.cycle + 1;
+            let during = self.registers;
+            self.step();
+            if probe.wants(cycle) {
+                trace.samples.push(Sample {
+                    cycle,
+                    during,
+                    after: self.registers,
+                });
+            }
+        }
+        trace
+    }
+
+    /// Carries out `ins`, returning where execution goes next.
+    fn execute(&mut self, ins: Instruction) -> usize {
+        let next = self.pc + 1;
+        match ins {
+            Instruction::Noop => next,
+            Instruction::Add(r, operand) => {
+                let value = self.value(operand);
+                let r = &mut self.registers[r.index()];
+                *r = r.wrapping_add(value);
+                next
+            }
+            Instruction::Mul(r, operand) => {
+                let value = self.value(operand);
+                let r = &mut self.registers[r.index()];
+                *r = r.wrapping_mul(value);
+                next
+            }
+            Instruction::Jmp(to) => to,
+            Instruction::Jz(r, to) if self.register(r) == 0 => to,
+            Instruction::Jnz(r, to) if self.register(r) != 0 => to,
+            Instruction::Jz(..) | Instruction::Jnz(..) => next,
+        }
+    }
+
+    fn value(&self, operand: Operand) -> i32 {
+        match operand {
+            Operand::Value(v) => v,
+            Operand::Register(r) => self.register(r),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(source: &str) -> MachineState {
+        let mut ms = MachineState::new(&Program::parse(source).unwrap());
+        while ms.step() {}
+        ms
+    }
+
+    #[test]
+    fn loop_with_conditional_jump() {
+        let ms = run("\
+    addy 3
+loop:
+    addx 2
+    addy -1
+    jnz y loop");
+
+        assert_eq!(ms.x(), 7);
+        assert_eq!(ms.register(Register::Y), 0);
+        // `addy` once, then three times round the loop.
+        assert_eq!(ms.cycle(), 2 + 3 * (2 + 2 + 2));
+    }
+
+    #[test]
+    fn registers_as_operands() {
+        let ms = run("\
+addy 5
+mulx y
+addz x
+mulz -2
+jnz z end
+addx 100
+end:");
+
+        assert_eq!(ms.x(), 5);
+        assert_eq!(ms.register(Register::Z), -10);
+        assert_eq!(ms.pc(), 6);
+    }
+
+    #[test]
+    fn jz_falls_through_unless_zero() {
+        let ms = run("\
+jz x +2
+addx 1
+jz z +2
+addx 10
+noop");
+        assert_eq!(ms.x(), 2);
+    }
+
+    #[test]
+    fn draws_on_a_custom_crt() {
+        let source = format!("addx 40\n{}", "noop\n".repeat(78));
+        let program = Program::parse(&source).unwrap();
+        let mut ms = MachineState::with_crt(&program, Crt::new(80, 1, 3));
+        while !ms.is_halted() {
+            ms.draw();
+            ms.step();
+        }
+
+        let expected = format!("##{}###{}", ".".repeat(38), ".".repeat(37));
+        assert_eq!(ms.render_display(), expected);
+    }
+
+    #[test]
+    fn probing_a_loop_stops_after_the_last_cycle() {
+        let program = Program::parse("loop:\n    addx 1\n    jz z loop").unwrap();
+        let mut ms = MachineState::new(&program);
+        let trace = ms.run_probed(&Probe::at([4, 8]));
+
+        assert_eq!(trace.during(8, Register::X), Some(3));
+        assert_eq!(ms.cycle(), 8);
+        assert!(!ms.is_halted());
+    }
+}
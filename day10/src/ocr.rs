@@ -0,0 +1,154 @@
+use crate::crt::Crt;
+
+const GLYPH_WIDTH: usize = 4;
+const GLYPH_HEIGHT: usize = 6;
+/// A glyph and the blank column after it.
+const CELL_WIDTH: usize = GLYPH_WIDTH + 1;
+
+/// The letters Advent of Code draws.
+#[rustfmt::skip]
+const FONT: [(char, [&str; GLYPH_HEIGHT]); 17] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum OcrError {
+    #[error("letters are {GLYPH_HEIGHT} pixels high, but the screen is {0}")]
+    WrongHeight(usize),
+
+    #[error("letter {position} isn't in the font:\n{bitmap}")]
+    #[diagnostic(help("the font has {}", FONT.iter().map(|(c, _)| c).collect::<String>()))]
+    UnknownGlyph {
+        /// Counting from 0, left to right.
+        position: usize,
+        /// The letter's cell as drawn, blank column included.
+        bitmap: String,
+    },
+}
+
+/// Reads the letters on the screen, one every 5 columns.
+pub fn read_letters(crt: &Crt) -> Result<String, OcrError> {
+    if crt.height() != GLYPH_HEIGHT {
+        return Err(OcrError::WrongHeight(crt.height()));
+    }
+    (0..crt.width().div_ceil(CELL_WIDTH))
+        .map(|position| read_letter(crt, position))
+        .collect()
+}
+
+fn read_letter(crt: &Crt, position: usize) -> Result<char, OcrError> {
+    let left = position * CELL_WIDTH;
+    let right = (left + CELL_WIDTH).min(crt.width());
+    let rows = (0..GLYPH_HEIGHT)
+        .map(|row| {
+            (left..right)
+                .map(|col| if crt.is_lit(row, col) { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+
+    let (glyph, gaps): (Vec<_>, Vec<_>) = rows
+        .iter()
+        .map(|row| row.split_at(GLYPH_WIDTH.min(row.len())))
+        .unzip();
+    let gap_is_blank = gaps.iter().all(|gap| !gap.contains('#'));
+
+    FONT.iter()
+        .find(|(_, bitmap)| gap_is_blank && *bitmap == glyph[..])
+        .map(|&(c, _)| c)
+        .ok_or_else(|| OcrError::UnknownGlyph {
+            position,
+            bitmap: rows.join("\n"),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A screen showing `rows`, drawn by putting the sprite over the lit
+    /// pixels as the beam passes.
+    fn screen(rows: &[String]) -> Crt {
+        let width = rows[0].len();
+        let mut crt = Crt::new(width, rows.len(), 1);
+        for (row, pixels) in rows.iter().enumerate() {
+            for (col, pixel) in pixels.chars().enumerate() {
+                let sprite_x = if pixel == '#' { col as i32 } else { -1 };
+                crt.draw((row * width + col) as u32, sprite_x);
+            }
+        }
+        crt
+    }
+
+    fn word(letters: &str) -> Vec<String> {
+        (0..GLYPH_HEIGHT)
+            .map(|row| {
+                letters
+                    .chars()
+                    .map(|c| {
+                        let (_, bitmap) = FONT.iter().find(|(l, _)| *l == c).unwrap();
+                        format!("{}.", bitmap[row])
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reads_every_letter() {
+        let all = FONT.iter().map(|(c, _)| c).collect::<String>();
+        assert_eq!(read_letters(&screen(&word(&all))).unwrap(), all);
+    }
+
+    #[test]
+    fn last_blank_column_is_optional() {
+        let rows = word("ZEPHR")
+            .into_iter()
+            .map(|mut row| {
+                row.pop();
+                row
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(read_letters(&screen(&rows)).unwrap(), "ZEPHR");
+    }
+
+    #[test]
+    fn unknown_glyph_is_reported_with_its_bitmap() {
+        let mut rows = word("ABC");
+        // Light up B's blank column.
+        rows[2].replace_range(9..10, "#");
+
+        match read_letters(&screen(&rows)).unwrap_err() {
+            OcrError::UnknownGlyph { position, bitmap } => {
+                assert_eq!(position, 1);
+                assert_eq!(bitmap, "###..\n#..#.\n###.#\n#..#.\n#..#.\n###..");
+            }
+            e => panic!("{e}"),
+        }
+    }
+
+    #[test]
+    fn needs_six_rows() {
+        let rows = word("A")[..5].to_vec();
+        assert!(matches!(
+            read_letters(&screen(&rows)),
+            Err(OcrError::WrongHeight(5))
+        ));
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::BTreeSet;
+
+use crate::isa::Register;
+
+/// Which cycles to sample, counting from 1 like the puzzle does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Probe {
+    At(BTreeSet<u32>),
+    /// Cycle `first` and every `period` cycles after it. A `period` of 0
+    /// samples `first` only.
+    Every {
+        first: u32,
+        period: u32,
+    },
+}
+
+impl Probe {
+    pub fn at(cycles: impl IntoIterator<Item = u32>) -> Self {
+        Probe::At(cycles.into_iter().collect())
+    }
+
+    pub fn every(first: u32, period: u32) -> Self {
+        Probe::Every { first, period }
+    }
+
+    pub fn wants(&self, cycle: u32) -> bool {
+        match *self {
+            Probe::At(ref cycles) => cycles.contains(&cycle),
+            Probe::Every { first, period: 0 } => cycle == first,
+            Probe::Every { first, period } => {
+                cycle >= first && (cycle - first).is_multiple_of(period)
+            }
+        }
+    }
+
+    /// The last cycle this samples, if it stops.
+    pub fn last(&self) -> Option<u32> {
+        match *self {
+            Probe::At(ref cycles) => Some(cycles.last().copied().unwrap_or(0)),
+            Probe::Every { first, period: 0 } => Some(first),
+            Probe::Every { .. } => None,
+        }
+    }
+}
+
+/// The registers during a cycle, and once it's over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sample {
+    /// Counting from 1.
+    pub cycle: u32,
+    pub(crate) during: [i32; Register::ALL.len()],
+    pub(crate) after: [i32; Register::ALL.len()],
+}
+
+impl Sample {
+    pub fn during(&self, r: Register) -> i32 {
+        self.during[r.index()]
+    }
+
+    /// Differs from [`Sample::during`] only if an instruction finished this
+    /// cycle.
+    pub fn after(&self, r: Register) -> i32 {
+        self.after[r.index()]
+    }
+
+    /// The cycle number times `x` during it. Wide enough for any of those.
+    pub fn signal_strength(&self) -> i64 {
+        i64::from(self.cycle) * i64::from(self.during(Register::X))
+    }
+}
+
+/// The samples a [`Probe`] took, in cycle order. Made by
+/// [`MachineState::run_probed`](crate::MachineState::run_probed).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProbeTrace {
+    pub(crate) samples: Vec<Sample>,
+}
+
+impl ProbeTrace {
+    pub fn samples(&self) -> &[Sample] {
+        &self.samples
+    }
+
+    /// The sample for `cycle`, if it was taken.
+    pub fn get(&self, cycle: u32) -> Option<&Sample> {
+        self.samples
+            .binary_search_by_key(&cycle, |s| s.cycle)
+            .ok()
+            .map(|i| &self.samples[i])
+    }
+
+    pub fn during(&self, cycle: u32, r: Register) -> Option<i32> {
+        self.get(cycle).map(|s| s.during(r))
+    }
+
+    pub fn after(&self, cycle: u32, r: Register) -> Option<i32> {
+        self.get(cycle).map(|s| s.after(r))
+    }
+
+    /// The sum of every sample's signal strength.
+    pub fn signal_strength(&self) -> i64 {
+        self.samples.iter().map(Sample::signal_strength).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{isa::Program, MachineState};
+
+    fn probe(source: &str, probe: Probe) -> ProbeTrace {
+        let mut ms = MachineState::new(&Program::parse(source).unwrap());
+        ms.run_probed(&probe)
+    }
+
+    #[test]
+    fn during_and_after() {
+        let trace = probe("noop\naddx 3\naddx -5", Probe::every(1, 1));
+
+        let x = |f: fn(&Sample, Register) -> i32| {
+            trace
+                .samples()
+                .iter()
+                .map(|s| f(s, Register::X))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(x(Sample::during), [1, 1, 1, 4, 4]);
+        assert_eq!(x(Sample::after), [1, 1, 4, 4, -1]);
+        assert_eq!(trace.samples().len(), 5);
+        assert_eq!(trace.get(6), None);
+    }
+
+    #[test]
+    fn example_signal_strengths() {
+        let trace = probe(
+            include_str!("example.txt"),
+            Probe::at((20..=220).step_by(40)),
+        );
+
+        let strengths = trace
+            .samples()
+            .iter()
+            .map(|s| (s.cycle, s.during(Register::X), s.signal_strength()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            strengths,
+            [
+                (20, 21, 420),
+                (60, 19, 1140),
+                (100, 18, 1800),
+                (140, 21, 2940),
+                (180, 16, 2880),
+                (220, 18, 3960),
+            ]
+        );
+        assert_eq!(trace.signal_strength(), 13140);
+    }
+
+    #[test]
+    fn every_n_cycles() {
+        let trace = probe(include_str!("example.txt"), Probe::every(20, 40));
+        let cycles = trace.samples().iter().map(|s| s.cycle).collect::<Vec<_>>();
+        assert_eq!(cycles, [20, 60, 100, 140, 180, 220]);
+        assert_eq!(trace.during(140, Register::X), Some(21));
+        assert_eq!(trace.during(141, Register::X), None);
+
+        let trace = probe(include_str!("example.txt"), Probe::every(7, 0));
+        assert_eq!(trace.samples().len(), 1);
+    }
+
+    #[test]
+    fn strengths_dont_overflow() {
+        let source = format!("addx {}\n{}", i32::MAX - 1, "noop\n".repeat(20));
+        let trace = probe(&source, Probe::at([20, 21]));
+        assert_eq!(trace.signal_strength(), 41 * i64::from(i32::MAX));
+    }
+}
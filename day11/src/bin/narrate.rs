@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+
+use aoc_core::{input::read_input_from, solution::Solution};
+use clap::Parser;
+use day11::{Day11, Relief, Simulation};
+
+/// Play the monkeys' game, describing every round the way the puzzle does
+#[derive(Parser)]
+struct Cli {
+    /// Puzzle input file, or `-` for stdin; defaults to the bundled
+    /// `src/input.txt`
+    input: Option<PathBuf>,
+
+    /// Rounds to play
+    #[arg(long, default_value_t = 20)]
+    rounds: usize,
+
+    /// Keep worry levels modulo the divisors' least common multiple, as in
+    /// part two, instead of dividing them by three
+    #[arg(long)]
+    modular: bool,
+}
+
+fn main() -> miette::Result<()> {
+    let cli = Cli::parse();
+    let path = cli
+        .input
+        .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt")));
+    let input = read_input_from(&path.to_string_lossy())?;
+
+    let monkeys = Day11::parse(&input)?;
+    let relief = if cli.modular {
+        Relief::Modular
+    } else {
+        Relief::DivideByThree
+    };
+    let mut sim: Simulation = Simulation::new(&monkeys, relief)?;
+
+    for _ in 0..cli.rounds {
+        print!("{}", sim.narrate_round()?);
+        println!();
+        print!("{}", sim.holdings());
+        println!();
+        print!("{}", sim.inspection_counts());
+        println!();
+    }
+    match sim.monkey_business() {
+        Some(business) => println!("Monkey business: {business}"),
+        None => println!("Monkey business: more than a u64 holds"),
+    }
+    Ok(())
+}
@@ -0,0 +1,295 @@
+use std::fmt;
+
+use aoc_core::{parse::BadInput, solution::Solution};
+use nom_supreme::{error::ErrorTree, final_parser::final_parser};
+
+pub mod metrics;
+pub use metrics::Activity;
+
+mod narrate;
+
+pub mod parse;
+use parse::{parse_all_monkeys, Monkey, Span};
+
+pub mod simulation;
+pub use simulation::{EvalError, Relief, SetupError, Simulation};
+
+pub mod trace;
+pub use trace::{Inspection, Trace};
+
+pub mod validate;
+use validate::validate;
+
+pub mod worry;
+pub use worry::{ArithError, Worry};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Vec<Monkey>;
+    type AnswerOne = u64;
+    type AnswerTwo = u64;
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        parse_monkeys(input)
+    }
+
+    fn part_one(monkeys: &Self::Input) -> miette::Result<u64> {
+        let mut sim: Simulation = Simulation::new(monkeys, Relief::DivideByThree)?;
+        monkey_business(sim.run(20)?)
+    }
+
+    fn part_two(monkeys: &Self::Input) -> miette::Result<u64> {
+        let mut sim: Simulation = Simulation::new(monkeys, Relief::Modular)?;
+        monkey_business(sim.run(10_000)?)
+    }
+}
+
+fn monkey_business(sim: &Simulation) -> miette::Result<u64> {
+    sim.monkey_business()
+        .ok_or_else(|| miette::miette!("monkey business doesn't fit in a u64"))
+}
+
+fn parse_monkeys(input: &str) -> miette::Result<Vec<Monkey>> {
+    let span = Span::new(input);
+
+    let monkeys_res: Result<_, ErrorTree<Span>> =
+        final_parser(parse_all_monkeys::<ErrorTree<Span>>)(span);
+    let monkeys = monkeys_res.map_err(|e| {
+        let e = e.map_locations(|location| location.location_offset());
+        BadInput::from_tree(input, e)
+    })?;
+
+    validate(input, &monkeys)?;
+    Ok(monkeys.into_iter().map(|(monkey, _)| monkey).collect())
+}
+
+/// Writes monkeys in the input's format, which [`Day11::parse`] reads back
+/// as the same monkeys.
+pub fn format_monkeys<W: fmt::Display>(monkeys: &[Monkey<W>]) -> String {
+    let mut out = monkeys
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parse::{BinOp, Expr, Operation, Term};
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part_one_example() {
+        let input = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(Day11::part_one(&input).unwrap(), 10605);
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(Day11::part_two(&input).unwrap(), 2713310158);
+    }
+
+    fn labels(input: &str) -> Vec<String> {
+        let report = Day11::parse(input).unwrap_err();
+        let bad_input = report.downcast_ref::<BadInput>().unwrap();
+        miette::Diagnostic::labels(bad_input)
+            .unwrap()
+            .filter_map(|label| label.label().map(str::to_owned))
+            .collect()
+    }
+
+    #[test]
+    fn bad_operator_labels_every_alternative_and_context() {
+        let labels = labels(&EXAMPLE.replacen("old * 19", "old % 19", 1));
+        for expected in [
+            "expected \"\\n\"",
+            "expected CRLF",
+            "in section \"operation\"",
+            "in section \"monkey\"",
+        ] {
+            assert!(
+                labels.iter().any(|l| l == expected),
+                "{expected} not in {labels:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn typo_in_a_tag_is_labelled() {
+        let labels = labels(&EXAMPLE.replacen("If true", "If ture", 1));
+        assert!(labels
+            .iter()
+            .any(|l| l == "expected \"If true: throw to monkey \""));
+        assert!(labels.iter().any(|l| l == "in section \"if true\""));
+    }
+
+    fn assert_parses_like_example(input: &str) {
+        let monkeys = Day11::parse(input).unwrap();
+        assert_eq!(monkeys.len(), 4);
+        assert_eq!(Day11::part_one(&monkeys).unwrap(), 10605);
+    }
+
+    #[test]
+    fn unix_line_endings() {
+        assert_parses_like_example(EXAMPLE);
+    }
+
+    #[test]
+    fn windows_line_endings() {
+        assert_parses_like_example(&EXAMPLE.replace('\n', "\r\n"));
+    }
+
+    #[test]
+    fn no_final_newline() {
+        assert_parses_like_example(EXAMPLE.trim_end());
+        assert_parses_like_example(EXAMPLE.replace('\n', "\r\n").trim_end());
+    }
+
+    #[test]
+    fn extra_blank_lines_at_the_end() {
+        assert_parses_like_example(&format!("{EXAMPLE}\n\n"));
+        assert_parses_like_example(&format!("{EXAMPLE}\r\n  \r\n"));
+    }
+
+    #[test]
+    fn trailing_whitespace() {
+        let input = EXAMPLE
+            .lines()
+            .map(|line| format!("{line}  \t\n"))
+            .collect::<String>();
+        assert_parses_like_example(&input);
+    }
+
+    #[test]
+    fn mistake_in_a_later_monkey_is_reported() {
+        let input = EXAMPLE.replace(
+            "Monkey 3:\n  Starting items: 74",
+            "Monkey 3:\n  Starting items: x",
+        );
+        let labels = labels(&input);
+        assert!(
+            labels.iter().any(|l| l == "in section \"starting items\""),
+            "{labels:?}"
+        );
+    }
+
+    #[test]
+    fn out_of_order_ids_are_reported() {
+        let input = EXAMPLE.replacen("Monkey 1:", "Monkey 7:", 1);
+        assert_eq!(labels(&input), ["expected monkey 1 here"]);
+    }
+
+    #[test]
+    fn missing_receiver_is_reported() {
+        let input = EXAMPLE.replacen("false: throw to monkey 3", "false: throw to monkey 4", 1);
+        let report = Day11::parse(&input).unwrap_err();
+        let bad_input = report.downcast_ref::<BadInput>().unwrap();
+        let mut labels = miette::Diagnostic::labels(bad_input).unwrap();
+        let label = labels.next().unwrap();
+
+        assert_eq!(label.label(), Some("there's no monkey 4"));
+        assert_eq!(&input[label.offset()..][..label.len()], "4");
+    }
+
+    #[test]
+    fn throwing_to_itself_is_reported() {
+        let input = EXAMPLE.replacen("true: throw to monkey 2", "true: throw to monkey 0", 1);
+        assert_eq!(labels(&input), ["monkey 0 would throw to itself"]);
+    }
+
+    #[test]
+    fn zero_divisor_is_reported() {
+        let input = EXAMPLE.replacen("divisible by 19", "divisible by 0", 1);
+        assert_eq!(labels(&input), ["can't test divisibility by zero"]);
+    }
+
+    #[test]
+    fn every_problem_is_reported() {
+        let input = EXAMPLE
+            .replacen("Monkey 1:", "Monkey 7:", 1)
+            .replacen("by 19", "by 0", 1);
+        assert_eq!(labels(&input).len(), 2);
+    }
+
+    #[test]
+    fn example_prints_as_it_was() {
+        let monkeys = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(format_monkeys(&monkeys), EXAMPLE);
+    }
+
+    #[test]
+    fn empty_hands_round_trip() {
+        let mut sim: Simulation =
+            Simulation::new(&Day11::parse(EXAMPLE).unwrap(), Relief::DivideByThree).unwrap();
+        sim.run(1).unwrap();
+        let after_round_one = sim.monkeys().to_vec();
+
+        let text = format_monkeys(&after_round_one);
+        assert!(text.contains("Monkey 2:\n  Starting items:\n"));
+        let mut reparsed = Day11::parse(&text).unwrap();
+        for (monkey, original) in reparsed.iter_mut().zip(&after_round_one) {
+            monkey.items_inspected = original.items_inspected;
+        }
+        assert_eq!(reparsed, after_round_one);
+    }
+
+    fn expr() -> impl Strategy<Value = Expr> {
+        let term = prop_oneof![Just(Term::Old), any::<u64>().prop_map(Term::Constant)]
+            .prop_map(Expr::Term);
+        let op = prop_oneof![
+            Just(BinOp::Add),
+            Just(BinOp::Sub),
+            Just(BinOp::Mul),
+            Just(BinOp::Div),
+            Just(BinOp::Pow),
+        ];
+        term.prop_recursive(4, 16, 2, move |inner| {
+            (op.clone(), inner.clone(), inner).prop_map(|(op, l, r)| Expr::binary(op, l, r))
+        })
+    }
+
+    fn monkeys() -> impl Strategy<Value = Vec<Monkey>> {
+        let monkey = (
+            prop::collection::vec(any::<u64>(), 0..5),
+            expr(),
+            1..=u64::MAX,
+            any::<usize>(),
+            any::<usize>(),
+        );
+        prop::collection::vec(monkey, 2..6).prop_map(|monkeys| {
+            let n = monkeys.len();
+            // Receivers are other monkeys, so that the monkeys are valid.
+            let other = move |id: usize, r: usize| (id + 1 + r % (n - 1)) % n;
+            monkeys
+                .into_iter()
+                .enumerate()
+                .map(|(id, (items, expr, divisor, if_true, if_false))| Monkey {
+                    id,
+                    items_inspected: 0,
+                    items,
+                    operation: Operation(expr),
+                    divisor,
+                    receiver_if_true: other(id, if_true),
+                    receiver_if_false: other(id, if_false),
+                })
+                .collect()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn printed_monkeys_parse_back_the_same(monkeys in monkeys()) {
+            let text = format_monkeys(&monkeys);
+            prop_assert_eq!(Day11::parse(&text).unwrap(), monkeys);
+        }
+    }
+}
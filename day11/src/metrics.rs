@@ -0,0 +1,147 @@
+use std::cmp::Reverse;
+
+use crate::{
+    simulation::{EvalError, Simulation},
+    worry::Worry,
+};
+
+/// How many items each monkey had inspected by the end of a round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Activity {
+    pub round: usize,
+    /// Indexed by monkey.
+    pub inspected: Vec<u64>,
+}
+
+impl Activity {
+    /// The `k` busiest monkeys with their inspection counts, busiest first.
+    /// Monkeys that are equally busy come in order.
+    pub fn top(&self, k: usize) -> Vec<(usize, u64)> {
+        let mut counts = self
+            .inspected
+            .iter()
+            .copied()
+            .enumerate()
+            .collect::<Vec<_>>();
+        counts.sort_by_key(|&(monkey, count)| (Reverse(count), monkey));
+        counts.truncate(k);
+        counts
+    }
+
+    /// The product of the `k` highest inspection counts; monkey business is
+    /// `business(2)`. `None` if it doesn't fit in a `u64`.
+    pub fn business(&self, k: usize) -> Option<u64> {
+        self.top(k)
+            .into_iter()
+            .try_fold(1_u64, |product, (_, count)| product.checked_mul(count))
+    }
+
+    /// How many items each monkey inspected after `earlier` and up to this.
+    pub fn since(&self, earlier: &Activity) -> Vec<u64> {
+        self.inspected
+            .iter()
+            .zip(&earlier.inspected)
+            .map(|(now, then)| now - then)
+            .collect()
+    }
+}
+
+/// Plays a simulation a round at a time, yielding its [`Activity`] after
+/// each. Made by [`Simulation::activity_series`].
+pub struct ActivitySeries<'a, W> {
+    pub(crate) sim: &'a mut Simulation<W>,
+    pub(crate) rounds_left: usize,
+}
+
+impl<W: Worry> Iterator for ActivitySeries<'_, W> {
+    type Item = Result<Activity, EvalError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rounds_left == 0 {
+            return None;
+        }
+        match self.sim.play_round() {
+            Ok(()) => {
+                self.rounds_left -= 1;
+                Some(Ok(self.sim.activity()))
+            }
+            Err(e) => {
+                self.rounds_left = 0;
+                Some(Err(e))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.rounds_left))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{simulation::Relief, Day11};
+    use aoc_core::solution::Solution;
+
+    fn example() -> Simulation {
+        let monkeys = Day11::parse(include_str!("example.txt")).unwrap();
+        Simulation::new(&monkeys, Relief::Modular).unwrap()
+    }
+
+    #[test]
+    fn series_after_every_round() {
+        let mut sim = example();
+        let series = sim
+            .activity_series(20)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(series.len(), 20);
+        assert_eq!(series[0].round, 1);
+        assert_eq!(series[0].inspected, [2, 4, 3, 6]);
+        assert_eq!(series[19].inspected, [99, 97, 8, 103]);
+        assert_eq!(series[19], sim.activity());
+
+        // Per-round counts add up to the totals.
+        let per_round = series
+            .windows(2)
+            .map(|w| w[1].since(&w[0]))
+            .collect::<Vec<_>>();
+        let sum = per_round
+            .iter()
+            .fold(series[0].inspected.clone(), |acc, r| {
+                acc.iter().zip(r).map(|(a, b)| a + b).collect()
+            });
+        assert_eq!(sum, series[19].inspected);
+    }
+
+    #[test]
+    fn series_is_lazy() {
+        let mut sim = example();
+        let third = sim.activity_series(1000).nth(2).unwrap().unwrap();
+        assert_eq!(third.round, 3);
+        assert_eq!(sim.rounds_played(), 3);
+    }
+
+    #[test]
+    fn series_stops_at_an_error() {
+        let monkeys = Day11::parse(include_str!("example.txt")).unwrap();
+        let mut sim = Simulation::new(&monkeys, Relief::custom(|w: u64| w)).unwrap();
+        let results = sim.activity_series(1000).collect::<Vec<_>>();
+        assert!(results.last().unwrap().is_err());
+        assert!(results.iter().rev().skip(1).all(Result::is_ok));
+    }
+
+    #[test]
+    fn top_k_and_business() {
+        let mut sim = example();
+        let activity = sim.run(10_000).unwrap().activity();
+
+        assert_eq!(activity.top(2), [(0, 52166), (3, 52013)]);
+        assert_eq!(activity.business(2), Some(2713310158));
+        assert_eq!(activity.business(2), sim.monkey_business());
+        assert_eq!(activity.business(0), Some(1));
+        assert_eq!(activity.business(4), Some(52166 * 47830 * 1938 * 52013));
+        assert_eq!(activity.top(10).len(), 4);
+    }
+}
@@ -0,0 +1,92 @@
+//! What happens in a round, in the puzzle's own words, so that a simulation
+//! can be diffed against the puzzle's walkthrough.
+
+use std::fmt::{Display, Write};
+
+use crate::{
+    parse::{BinOp, Expr, Monkey, Operation, Term},
+    simulation::Relief,
+};
+
+pub(crate) fn monkey(out: &mut String, monkey: usize) {
+    let _ = writeln!(out, "Monkey {monkey}:");
+}
+
+pub(crate) fn inspection<W: Display>(out: &mut String, item: &W) {
+    let _ = writeln!(
+        out,
+        "  Monkey inspects an item with a worry level of {item}."
+    );
+}
+
+pub(crate) fn operation<W: Display>(out: &mut String, operation: &Operation, new: &W) {
+    let change = match &operation.0 {
+        Expr::Binary(op, lhs, rhs) if **lhs == Expr::Term(Term::Old) => match (op, &**rhs) {
+            (BinOp::Add, Expr::Term(Term::Constant(c))) => format!("increases by {c}"),
+            (BinOp::Sub, Expr::Term(Term::Constant(c))) => format!("decreases by {c}"),
+            (BinOp::Mul, Expr::Term(Term::Constant(c))) => format!("is multiplied by {c}"),
+            (BinOp::Mul, Expr::Term(Term::Old)) => "is multiplied by itself".to_owned(),
+            (BinOp::Div, Expr::Term(Term::Constant(c))) => format!("is divided by {c}"),
+            (BinOp::Pow, Expr::Term(Term::Constant(c))) => format!("is raised to the power {c}"),
+            _ => "changes".to_owned(),
+        },
+        _ => "changes".to_owned(),
+    };
+    let _ = writeln!(out, "    Worry level {change} to {new}.");
+}
+
+pub(crate) fn relief<W: Display>(out: &mut String, relief: &Relief<W>, modulus: u64, item: &W) {
+    let change = match relief {
+        Relief::DivideByThree => "is divided by 3".to_owned(),
+        Relief::Modular => format!("is reduced modulo {modulus}"),
+        Relief::Custom(_) => "changes".to_owned(),
+    };
+    let _ = writeln!(
+        out,
+        "    Monkey gets bored with item. Worry level {change} to {item}."
+    );
+}
+
+pub(crate) fn test(out: &mut String, divisible: bool, divisor: u64) {
+    let not = if divisible { "" } else { "not " };
+    let _ = writeln!(
+        out,
+        "    Current worry level is {not}divisible by {divisor}."
+    );
+}
+
+pub(crate) fn throw<W: Display>(out: &mut String, item: &W, receiver: usize) {
+    let _ = writeln!(
+        out,
+        "    Item with worry level {item} is thrown to monkey {receiver}."
+    );
+}
+
+/// The items each monkey holds after `round`.
+pub(crate) fn holdings<W: Display>(round: usize, monkeys: &[Monkey<W>]) -> String {
+    let mut out =
+        format!("After round {round}, the monkeys are holding items with these worry levels:\n");
+    for (i, mc) in monkeys.iter().enumerate() {
+        let items = mc
+            .items
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let _ = writeln!(out, "Monkey {i}: {items}");
+    }
+    out
+}
+
+/// How many items each monkey has inspected by the end of `round`.
+pub(crate) fn inspection_counts<W>(round: usize, monkeys: &[Monkey<W>]) -> String {
+    let mut out = format!("== After round {round} ==\n");
+    for (i, mc) in monkeys.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "Monkey {i} inspected items {} times.",
+            mc.items_inspected
+        );
+    }
+    out
+}
@@ -1,126 +1,430 @@
 use nom::{
     branch::alt,
-    bytes::complete::tag,
     character::complete as cc,
-    character::complete::{one_of, space1},
-    combinator::{map, value},
-    error::ParseError,
-    multi::separated_list1,
-    sequence::{preceded, tuple},
+    character::complete::{char, multispace0, one_of, space0, space1},
+    combinator::{consumed, cut, eof, map, map_opt, not, opt, value},
+    error::{context, ContextError, ParseError},
+    multi::{many1, separated_list0, separated_list1},
+    sequence::{delimited, pair, preceded, terminated, tuple},
     IResult,
 };
 
+use std::fmt;
+
+use miette::SourceSpan;
 use nom_locate::LocatedSpan;
+use nom_supreme::tag::{complete::tag, TagError};
+
+use crate::worry::{ArithError, Worry};
 
 pub type Span<'a> = LocatedSpan<&'a str>;
 
-#[derive(Debug, Clone)]
-pub struct Monkey {
+/// What the parsers below need from their error type, so that failures say
+/// which tag was expected and which part of a monkey was being parsed.
+/// `ErrorTree` has all of it.
+pub trait MonkeyError<'a>:
+    ParseError<Span<'a>> + ContextError<Span<'a>> + TagError<Span<'a>, &'static str>
+{
+}
+
+impl<'a, E> MonkeyError<'a> for E where
+    E: ParseError<Span<'a>> + ContextError<Span<'a>> + TagError<Span<'a>, &'static str>
+{
+}
+
+/// A monkey as described by the input. Items are plain `u64`s when parsed; a
+/// simulation may hold them as some other [`Worry`] type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Monkey<W = u64> {
+    pub id: usize,
     pub items_inspected: u64,
-    pub items: Vec<u64>,
+    pub items: Vec<W>,
     pub operation: Operation,
     pub divisor: u64,
     pub receiver_if_true: usize,
     pub receiver_if_false: usize,
 }
 
+/// Where the numbers that refer to monkeys were found in the input, so that
+/// ones that don't make sense can be pointed at.
 #[derive(Debug, Clone, Copy)]
-pub enum Operation {
-    Add(Term, Term),
-    Mul(Term, Term),
+pub struct MonkeySpans {
+    pub id: SourceSpan,
+    pub divisor: SourceSpan,
+    pub receiver_if_true: SourceSpan,
+    pub receiver_if_false: SourceSpan,
 }
 
+fn source_span(span: Span) -> SourceSpan {
+    (span.location_offset(), span.fragment().len()).into()
+}
+
+/// `new = <expression>`: how worry about an item changes as it's inspected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Operation(pub Expr);
+
 impl Operation {
-    pub fn eval(self, old: u64) -> u64 {
+    pub fn eval<W: Worry>(&self, old: &W) -> Result<W, ArithError> {
+        self.0.eval(old)
+    }
+}
+
+/// An arithmetic expression over `old`, the worry level before the operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Term(Term),
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    pub fn binary(op: BinOp, lhs: Expr, rhs: Expr) -> Self {
+        Expr::Binary(op, Box::new(lhs), Box::new(rhs))
+    }
+
+    pub fn eval<W: Worry>(&self, old: &W) -> Result<W, ArithError> {
         match self {
-            Operation::Add(l, r) => l.eval(old) + r.eval(old),
-            Operation::Mul(l, r) => l.eval(old) * r.eval(old),
+            Expr::Term(t) => Ok(t.eval(old)),
+            Expr::Binary(op, l, r) => W::apply(*op, &l.eval(old)?, &r.eval(old)?),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    /// Integer division, rounding down.
+    Div,
+    Pow,
+}
+
+impl BinOp {
+    /// How tightly the operator binds, as [`parse_expr`] has it.
+    fn precedence(self) -> u8 {
+        match self {
+            BinOp::Add | BinOp::Sub => 1,
+            BinOp::Mul | BinOp::Div => 2,
+            BinOp::Pow => 3,
+        }
+    }
+
+    fn is_right_assoc(self) -> bool {
+        self == BinOp::Pow
+    }
+
+    fn from_symbol(c: char) -> Option<Self> {
+        match c {
+            '+' => Some(BinOp::Add),
+            '-' => Some(BinOp::Sub),
+            '*' => Some(BinOp::Mul),
+            '/' => Some(BinOp::Div),
+            '^' => Some(BinOp::Pow),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Term {
     Old,
     Constant(u64),
 }
 
 impl Term {
-    pub fn eval(self, old: u64) -> u64 {
+    pub fn eval<W: Worry>(self, old: &W) -> W {
         match self {
-            Term::Old => old,
-            Term::Constant(c) => c,
+            Term::Old => old.clone(),
+            Term::Constant(c) => W::from_u64(c),
         }
     }
 }
 
-pub fn parse_term<'a, E: ParseError<Span<'a>>>(
-    i: Span<'a>,
-) -> IResult<Span<'a>, Term, E> {
+/// Writes a monkey the way the input describes it, without a final newline.
+/// Inspection counts aren't part of that.
+impl<W: fmt::Display> fmt::Display for Monkey<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Monkey {}:", self.id)?;
+        write!(f, "  Starting items:")?;
+        for (i, item) in self.items.iter().enumerate() {
+            let sep = if i == 0 { " " } else { ", " };
+            write!(f, "{sep}{item}")?;
+        }
+        writeln!(f)?;
+        writeln!(f, "  Operation: {}", self.operation)?;
+        writeln!(f, "  Test: divisible by {}", self.divisor)?;
+        writeln!(f, "    If true: throw to monkey {}", self.receiver_if_true)?;
+        write!(
+            f,
+            "    If false: throw to monkey {}",
+            self.receiver_if_false
+        )
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "new = {}", self.0)
+    }
+}
+
+/// Writes the expression with only the parentheses it needs to parse back
+/// the same.
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Term(t) => write!(f, "{t}"),
+            Expr::Binary(op, l, r) => {
+                let (left_min, right_min) = if op.is_right_assoc() {
+                    (op.precedence() + 1, op.precedence())
+                } else {
+                    (op.precedence(), op.precedence() + 1)
+                };
+                write_operand(f, l, left_min)?;
+                write!(f, " {op} ")?;
+                write_operand(f, r, right_min)
+            }
+        }
+    }
+}
+
+/// Writes `expr`, in parentheses if it binds looser than `min_precedence`.
+fn write_operand(f: &mut fmt::Formatter<'_>, expr: &Expr, min_precedence: u8) -> fmt::Result {
+    match expr {
+        Expr::Binary(op, ..) if op.precedence() < min_precedence => write!(f, "({expr})"),
+        _ => write!(f, "{expr}"),
+    }
+}
+
+impl fmt::Display for BinOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            BinOp::Add => '+',
+            BinOp::Sub => '-',
+            BinOp::Mul => '*',
+            BinOp::Div => '/',
+            BinOp::Pow => '^',
+        };
+        write!(f, "{symbol}")
+    }
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::Old => write!(f, "old"),
+            Term::Constant(c) => write!(f, "{c}"),
+        }
+    }
+}
+
+/// The end of a line, `\n` or `\r\n`, allowing trailing spaces before it.
+fn eol<'a, E: MonkeyError<'a>>(i: Span<'a>) -> IResult<Span<'a>, (), E> {
+    value((), terminated(space0, alt((tag("\n"), tag("\r\n")))))(i)
+}
+
+pub fn parse_term<'a, E: MonkeyError<'a>>(i: Span<'a>) -> IResult<Span<'a>, Term, E> {
     alt((value(Term::Old, tag("old")), map(cc::u64, Term::Constant)))(i)
 }
 
-pub fn parse_operation<'a, E: ParseError<Span<'a>>>(
-    i: Span<'a>,
-) -> IResult<Span<'a>, Operation, E> {
-    let (i, (l, op, r)) = preceded(
-        tag("new = "),
-        tuple((
-            parse_term,
-            preceded(space1, one_of("*+")),
-            preceded(space1, parse_term),
-        )),
-    )(i)?;
-    let op = match op {
-        '*' => Operation::Mul(l, r),
-        '+' => Operation::Add(l, r),
-        _ => unreachable!(),
+pub fn parse_operation<'a, E: MonkeyError<'a>>(i: Span<'a>) -> IResult<Span<'a>, Operation, E> {
+    map(preceded(tag("new = "), parse_expr), Operation)(i)
+}
+
+/// Parses an expression with the usual precedence: `+` and `-` bind loosest,
+/// then `*` and `/`, then `^`. All are left-associative except `^`.
+pub fn parse_expr<'a, E: MonkeyError<'a>>(i: Span<'a>) -> IResult<Span<'a>, Expr, E> {
+    left_assoc(parse_product, "+-")(i)
+}
+
+fn parse_product<'a, E: MonkeyError<'a>>(i: Span<'a>) -> IResult<Span<'a>, Expr, E> {
+    left_assoc(parse_power, "*/")(i)
+}
+
+fn parse_power<'a, E: MonkeyError<'a>>(i: Span<'a>) -> IResult<Span<'a>, Expr, E> {
+    let (i, base) = parse_atom(i)?;
+    let (i, exponent) = opt(preceded(binary_op("^"), parse_power))(i)?;
+    let expr = match exponent {
+        Some(exponent) => Expr::binary(BinOp::Pow, base, exponent),
+        None => base,
     };
-    Ok((i, op))
+    Ok((i, expr))
 }
 
-pub fn parse_monkey<'a, E: ParseError<Span<'a>>>(
+fn parse_atom<'a, E: MonkeyError<'a>>(i: Span<'a>) -> IResult<Span<'a>, Expr, E> {
+    alt((
+        map(parse_term, Expr::Term),
+        delimited(pair(char('('), space0), parse_expr, pair(space0, char(')'))),
+    ))(i)
+}
+
+/// One of the operators in `symbols`, with any spaces around it.
+fn binary_op<'a, E: MonkeyError<'a>>(
+    symbols: &'static str,
+) -> impl FnMut(Span<'a>) -> IResult<Span<'a>, BinOp, E> {
+    delimited(space0, map_opt(one_of(symbols), BinOp::from_symbol), space0)
+}
+
+/// `operand`s joined by the operators in `symbols`, grouped from the left.
+fn left_assoc<'a, E: MonkeyError<'a>>(
+    mut operand: impl FnMut(Span<'a>) -> IResult<Span<'a>, Expr, E>,
+    symbols: &'static str,
+) -> impl FnMut(Span<'a>) -> IResult<Span<'a>, Expr, E> {
+    move |i| {
+        let (mut i, mut lhs) = operand(i)?;
+        loop {
+            match pair(binary_op(symbols), &mut operand)(i) {
+                Ok((rest, (op, rhs))) => {
+                    lhs = Expr::binary(op, lhs, rhs);
+                    i = rest;
+                }
+                Err(nom::Err::Error(_)) => return Ok((i, lhs)),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+pub fn parse_monkey<'a, E: MonkeyError<'a>>(
     i: Span<'a>,
-) -> IResult<Span<'a>, Monkey, E> {
-    let (i, _) = tuple((tag("Monkey "), cc::u64, tag(":"), tag("\r\n")))(i)?;
-    let (i, (_, _, items, _)) = tuple((
-        space1,
-        tag("Starting items: "),
-        separated_list1(tag(", "), cc::u64),
-        tag("\r\n"),
-    ))(i)?;
-    let (i, (_, _, operation, _)) =
-        tuple((space1, tag("Operation: "), parse_operation, tag("\r\n")))(i)?;
-    let (i, (_, _, divisor, _)) =
-        tuple((space1, tag("Test: divisible by "), cc::u64, tag("\r\n")))(i)?;
-    let (i, (_, _, receiver_if_true, _)) = tuple((
-        space1,
-        tag("If true: throw to monkey "),
-        map(cc::u64, |x| x as usize),
-        tag("\r\n"),
-    ))(i)?;
-    let (i, (_, _, receiver_if_false, _)) = tuple((
-        space1,
-        tag("If false: throw to monkey "),
-        map(cc::u64, |x| x as usize),
-        tag("\r\n"),
-    ))(i)?;
+) -> IResult<Span<'a>, (Monkey, MonkeySpans), E> {
+    let (i, (_, (id_span, id), _, _)) = context(
+        "monkey header",
+        tuple((tag("Monkey "), consumed(cc::u64), tag(":"), eol)),
+    )(i)?;
+    // A monkey may hold nothing, although none does at the start of the
+    // puzzle.
+    let (i, (_, _, items, _)) = context(
+        "starting items",
+        tuple((
+            space1,
+            tag("Starting items:"),
+            preceded(space0, separated_list0(tag(", "), cc::u64)),
+            eol,
+        )),
+    )(i)?;
+    let (i, (_, _, operation, _)) = context(
+        "operation",
+        tuple((space1, tag("Operation: "), parse_operation, eol)),
+    )(i)?;
+    let (i, (_, _, (divisor_span, divisor), _)) = context(
+        "test",
+        tuple((space1, tag("Test: divisible by "), consumed(cc::u64), eol)),
+    )(i)?;
+    let (i, (_, _, (if_true_span, receiver_if_true), _)) = context(
+        "if true",
+        tuple((
+            space1,
+            tag("If true: throw to monkey "),
+            consumed(map(cc::u64, |x| x as usize)),
+            eol,
+        )),
+    )(i)?;
+    let (i, (_, _, (if_false_span, receiver_if_false), _)) = context(
+        "if false",
+        tuple((
+            space1,
+            tag("If false: throw to monkey "),
+            consumed(map(cc::u64, |x| x as usize)),
+            space0,
+        )),
+    )(i)?;
 
     Ok((
         i,
-        Monkey {
-            items_inspected: 0,
-            items,
-            operation,
-            divisor,
-            receiver_if_true,
-            receiver_if_false,
-        },
+        (
+            Monkey {
+                id: id as usize,
+                items_inspected: 0,
+                items,
+                operation,
+                divisor,
+                receiver_if_true,
+                receiver_if_false,
+            },
+            MonkeySpans {
+                id: source_span(id_span),
+                divisor: source_span(divisor_span),
+                receiver_if_true: source_span(if_true_span),
+                receiver_if_false: source_span(if_false_span),
+            },
+        ),
     ))
 }
 
-pub fn parse_all_monkeys<'a, E: ParseError<Span<'a>>>(
+pub fn parse_all_monkeys<'a, E: MonkeyError<'a>>(
     i: Span<'a>,
-) -> IResult<Span<'a>, Vec<Monkey>, E> {
-    separated_list1(tag("\r\n"), parse_monkey)(i)
-}
\ No newline at end of file
+) -> IResult<Span<'a>, Vec<(Monkey, MonkeySpans)>, E> {
+    // Monkeys are separated by blank lines, but blank lines at the very end
+    // aren't a separator. Once past one, a monkey has to follow, so a mistake
+    // in any monkey is reported as such rather than as unexpected trailing
+    // input.
+    let separator = tuple((eol, many1(eol), not(pair(multispace0, eof))));
+    delimited(
+        multispace0,
+        separated_list1(separator, cut(context("monkey", parse_monkey))),
+        multispace0,
+    )(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom_supreme::{error::ErrorTree, final_parser::final_parser};
+
+    fn expr(expr: &str) -> Expr {
+        let expr: Result<Expr, ErrorTree<Span>> =
+            final_parser(parse_expr::<ErrorTree<Span>>)(Span::new(expr));
+        expr.unwrap()
+    }
+
+    fn eval(e: &str, old: u64) -> u64 {
+        expr(e).eval(&old).unwrap()
+    }
+
+    #[test]
+    fn original_operations() {
+        assert_eq!(eval("old * 19", 2), 38);
+        assert_eq!(eval("old + 6", 2), 8);
+        assert_eq!(eval("old * old", 3), 9);
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(eval("old + 2 * 3", 1), 7);
+        assert_eq!(eval("old * 2 + 3", 1), 5);
+        assert_eq!(eval("2 * old ^ 2", 3), 18);
+        assert_eq!(eval("old - 6 / 2", 10), 7);
+    }
+
+    #[test]
+    fn associativity() {
+        assert_eq!(eval("old - 10 - 3", 20), 7);
+        assert_eq!(eval("old / 2 / 2", 20), 5);
+        assert_eq!(eval("2 ^ 3 ^ 2", 0), 512);
+    }
+
+    #[test]
+    fn parentheses() {
+        assert_eq!(eval("(old + 2) * 3", 1), 9);
+        assert_eq!(eval("( old+2 )*( old-1 )", 4), 18);
+        assert_eq!(eval("((old))", 5), 5);
+    }
+
+    #[test]
+    fn printing_keeps_only_needed_parentheses() {
+        for (input, printed) in [
+            ("old*19", "old * 19"),
+            ("( old+2 )*( old-1 )", "(old + 2) * (old - 1)"),
+            ("(old - 10) - 3", "old - 10 - 3"),
+            ("old - (10 - 3)", "old - (10 - 3)"),
+            ("old + (2 * 3)", "old + 2 * 3"),
+            ("2 ^ (3 ^ 2)", "2 ^ 3 ^ 2"),
+            ("(2 ^ 3) ^ 2", "(2 ^ 3) ^ 2"),
+            ("((old))", "old"),
+        ] {
+            assert_eq!(expr(input).to_string(), printed);
+            assert_eq!(expr(printed), expr(input));
+        }
+    }
+}
@@ -0,0 +1,813 @@
+//! Playing out the monkeys' rounds. [`Simulation::run`] plays round by
+//! round; [`Simulation::run_parallel`] and [`Simulation::fast_forward`]
+//! instead follow each item through all the rounds on its own, which gives
+//! the same inspection counts since items never affect each other.
+//!
+//! Following items one by one may leave them in a different order within a
+//! monkey's hands than `run` would, which changes nothing about later rounds.
+//! On an error, nothing changes, and the error reported is the one `run`
+//! would have stopped at. While tracing, every inspection has to be
+//! recorded, so both just call `run`.
+
+use std::{collections::HashMap, fmt, hash::Hash};
+
+use rayon::prelude::*;
+
+use crate::{
+    metrics::{Activity, ActivitySeries},
+    narrate,
+    parse::Monkey,
+    trace::{Inspection, Trace},
+    worry::{ArithError, Worry},
+};
+
+/// How worry about an item drops once a monkey has inspected it and got bored.
+pub enum Relief<W = u64> {
+    /// Part one: worry is divided by three, rounding down.
+    DivideByThree,
+
+    /// Part two: worry doesn't drop, but is kept modulo the least common
+    /// multiple of the monkeys' divisors, which changes none of the tests'
+    /// outcomes.
+    Modular,
+
+    /// Any other rule, for experimenting.
+    Custom(Box<dyn Fn(W) -> W + Send + Sync>),
+}
+
+impl<W: Worry> Relief<W> {
+    pub fn custom(f: impl Fn(W) -> W + Send + Sync + 'static) -> Self {
+        Self::Custom(Box::new(f))
+    }
+
+    fn apply(&self, worry: W, modulus: u64) -> W {
+        match self {
+            Relief::DivideByThree => worry.div_u64(3),
+            Relief::Modular => worry.rem_u64(modulus),
+            Relief::Custom(f) => f(worry),
+        }
+    }
+}
+
+impl<W> fmt::Debug for Relief<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Relief::DivideByThree => write!(f, "DivideByThree"),
+            Relief::Modular => write!(f, "Modular"),
+            Relief::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+/// A monkey's operation failed on one of its items.
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+#[error("in round {round}, monkey {monkey}'s operation {kind} on an item with worry level {item}")]
+#[diagnostic(help(
+    "relief such as `Relief::Modular` keeps worry levels small; \
+     the `bigint` feature lets them grow without overflowing"
+))]
+pub struct EvalError {
+    pub round: usize,
+    pub monkey: usize,
+    /// The item's worry level before the operation.
+    pub item: String,
+    pub kind: ArithError,
+}
+
+/// Why monkeys can't be simulated.
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum SetupError {
+    #[error("monkey {monkey} tests divisibility by zero")]
+    ZeroDivisor { monkey: usize },
+
+    /// The monkeys' divisors have no common multiple small enough for
+    /// [`Relief::Modular`] to keep worry levels modulo.
+    #[error("the least common multiple of the monkeys' divisors doesn't fit in a u64")]
+    #[diagnostic(help("try another relief policy, such as `Relief::DivideByThree`"))]
+    ModulusOverflow,
+}
+
+/// Monkeys throwing items at each other, a round at a time.
+#[derive(Debug)]
+pub struct Simulation<W = u64> {
+    monkeys: Vec<Monkey<W>>,
+    relief: Relief<W>,
+    /// Least common multiple of every monkey's divisor for
+    /// [`Relief::Modular`], and 1 for any other relief.
+    modulus: u64,
+    rounds_played: usize,
+    trace: Option<Trace>,
+    /// Which item is which, alongside each monkey's items, while tracing.
+    item_ids: Vec<Vec<usize>>,
+}
+
+impl<W: Worry> Simulation<W> {
+    /// Fails if a monkey's divisor is zero or, for [`Relief::Modular`], if
+    /// the divisors have no common multiple that fits in a `u64`.
+    pub fn new(monkeys: &[Monkey], relief: Relief<W>) -> Result<Self, SetupError> {
+        if let Some(m) = monkeys.iter().find(|m| m.divisor == 0) {
+            return Err(SetupError::ZeroDivisor { monkey: m.id });
+        }
+
+        let monkeys = monkeys
+            .iter()
+            .map(|m| Monkey {
+                id: m.id,
+                items_inspected: m.items_inspected,
+                items: m.items.iter().map(|&i| W::from_u64(i)).collect(),
+                operation: m.operation.clone(),
+                divisor: m.divisor,
+                receiver_if_true: m.receiver_if_true,
+                receiver_if_false: m.receiver_if_false,
+            })
+            .collect::<Vec<_>>();
+
+        let modulus = match relief {
+            Relief::Modular => monkeys
+                .iter()
+                .try_fold(1, |lcm, m| checked_lcm(lcm, m.divisor))
+                .ok_or(SetupError::ModulusOverflow)?,
+            _ => 1,
+        };
+
+        Ok(Self {
+            modulus,
+            monkeys,
+            relief,
+            rounds_played: 0,
+            trace: None,
+            item_ids: vec![],
+        })
+    }
+
+    /// Starts recording every inspection from here on. Does nothing if
+    /// already tracing.
+    pub fn start_trace(&mut self) {
+        if self.trace.is_some() {
+            return;
+        }
+        let mut next_id = 0..;
+        self.item_ids = self
+            .monkeys
+            .iter()
+            .map(|m| next_id.by_ref().take(m.items.len()).collect())
+            .collect();
+        self.trace = Some(Trace::default());
+    }
+
+    pub fn trace(&self) -> Option<&Trace> {
+        self.trace.as_ref()
+    }
+
+    pub fn monkeys(&self) -> &[Monkey<W>] {
+        &self.monkeys
+    }
+
+    pub fn rounds_played(&self) -> usize {
+        self.rounds_played
+    }
+
+    /// Plays `rounds` more rounds, stopping at the first error.
+    pub fn run(&mut self, rounds: usize) -> Result<&mut Self, EvalError> {
+        for _ in 0..rounds {
+            self.play_round()?;
+        }
+        Ok(self)
+    }
+
+    /// Plays one round: each monkey in turn inspects and throws all the items
+    /// it holds. Relief is applied after the monkey's operation and before
+    /// its test. On an error, the simulation is left as it was before the
+    /// round.
+    pub fn play_round(&mut self) -> Result<(), EvalError> {
+        self.play_round_narrated(None)
+    }
+
+    /// Plays one round like [`play_round`](Self::play_round), describing
+    /// every inspection the way the puzzle does.
+    pub fn narrate_round(&mut self) -> Result<String, EvalError> {
+        let mut narration = String::new();
+        self.play_round_narrated(Some(&mut narration))?;
+        Ok(narration)
+    }
+
+    /// The items each monkey holds, as the puzzle lists them after a round.
+    pub fn holdings(&self) -> String {
+        narrate::holdings(self.rounds_played, &self.monkeys)
+    }
+
+    /// How many items each monkey has inspected, as the puzzle lists them
+    /// after a round.
+    pub fn inspection_counts(&self) -> String {
+        narrate::inspection_counts(self.rounds_played, &self.monkeys)
+    }
+
+    fn play_round_narrated(&mut self, narration: Option<&mut String>) -> Result<(), EvalError> {
+        // Items thrown to later monkeys are inspected again this round, so
+        // there's no checking every inspection up front; an error puts
+        // everything back instead.
+        let items = self
+            .monkeys
+            .iter()
+            .map(|m| (m.items.clone(), m.items_inspected))
+            .collect::<Vec<_>>();
+        let item_ids = self.item_ids.clone();
+        let recorded = self.trace.as_ref().map_or(0, |t| t.inspections().len());
+
+        let res = self.play_monkeys(narration);
+        if res.is_err() {
+            for (mc, (items, inspected)) in self.monkeys.iter_mut().zip(items) {
+                mc.items = items;
+                mc.items_inspected = inspected;
+            }
+            self.item_ids = item_ids;
+            if let Some(trace) = &mut self.trace {
+                trace.truncate(recorded);
+            }
+        }
+        res
+    }
+
+    /// Every monkey's turn in a round, in order.
+    fn play_monkeys(&mut self, mut narration: Option<&mut String>) -> Result<(), EvalError> {
+        for i in 0..self.monkeys.len() {
+            if let Some(out) = narration.as_deref_mut() {
+                narrate::monkey(out, i);
+            }
+
+            let items = std::mem::take(&mut self.monkeys[i].items);
+            self.monkeys[i].items_inspected += items.len() as u64;
+
+            let befores = match self.trace {
+                Some(_) => items.iter().map(ToString::to_string).collect(),
+                None => vec![],
+            };
+
+            let round = self.rounds_played + 1;
+            let thrown = items
+                .into_iter()
+                .map(|item| self.inspect(i, item, round, narration.as_deref_mut()))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if let Some(trace) = &mut self.trace {
+                let ids = std::mem::take(&mut self.item_ids[i]);
+                for ((id, before), (receiver, after)) in ids.into_iter().zip(befores).zip(&thrown) {
+                    trace.record(Inspection {
+                        item: id,
+                        round: self.rounds_played + 1,
+                        monkey: i,
+                        before,
+                        after: after.to_string(),
+                        thrown_to: *receiver,
+                    });
+                    self.item_ids[*receiver].push(id);
+                }
+            }
+
+            for (receiver, item) in thrown {
+                self.monkeys[receiver].items.push(item);
+            }
+        }
+        self.rounds_played += 1;
+        Ok(())
+    }
+
+    /// Monkey `monkey` inspecting `item` in round `round`: returns who it
+    /// throws the item to, and the item's new worry level.
+    fn inspect(
+        &self,
+        monkey: usize,
+        item: W,
+        round: usize,
+        mut narration: Option<&mut String>,
+    ) -> Result<(usize, W), EvalError> {
+        let mc = &self.monkeys[monkey];
+        if let Some(out) = narration.as_deref_mut() {
+            narrate::inspection(out, &item);
+        }
+
+        let new = mc.operation.eval(&item).map_err(|kind| EvalError {
+            round,
+            monkey,
+            item: item.to_string(),
+            kind,
+        })?;
+        if let Some(out) = narration.as_deref_mut() {
+            narrate::operation(out, &mc.operation, &new);
+        }
+
+        let item = self.relief.apply(new, self.modulus);
+        let divisible = item.is_multiple_of(mc.divisor);
+        let receiver = if divisible {
+            mc.receiver_if_true
+        } else {
+            mc.receiver_if_false
+        };
+
+        if let Some(out) = narration {
+            narrate::relief(out, &self.relief, self.modulus, &item);
+            narrate::test(out, divisible, mc.divisor);
+            narrate::throw(out, &item, receiver);
+        }
+        Ok((receiver, item))
+    }
+
+    /// Plays `rounds` more rounds like [`run`](Self::run), following the
+    /// items on as many threads as there are cores. See the
+    /// [module docs](self) for how that differs from `run`.
+    pub fn run_parallel(&mut self, rounds: usize) -> Result<&mut Self, EvalError> {
+        if self.trace.is_some() {
+            return self.run(rounds);
+        }
+
+        let journeys = self
+            .held_items()
+            .into_par_iter()
+            .map(|item| self.play_item(item, rounds))
+            .collect();
+        self.finish_journeys(journeys, rounds)
+    }
+
+    /// Every item, with the monkey holding it.
+    fn held_items(&self) -> Vec<(usize, W)> {
+        self.monkeys
+            .iter()
+            .enumerate()
+            .flat_map(|(m, mc)| mc.items.iter().map(move |item| (m, item.clone())))
+            .collect()
+    }
+
+    /// Moves every item to where its journey ended and adds up the
+    /// inspections. If any item's journey failed, nothing changes and the
+    /// earliest failure is returned.
+    fn finish_journeys(
+        &mut self,
+        journeys: Vec<Result<Journey<W>, EvalError>>,
+        rounds: usize,
+    ) -> Result<&mut Self, EvalError> {
+        let (journeys, errors): (Vec<_>, Vec<_>) = journeys.into_iter().partition(Result::is_ok);
+        if let Some(error) = errors
+            .into_iter()
+            .filter_map(Result::err)
+            .min_by_key(|e| (e.round, e.monkey))
+        {
+            return Err(error);
+        }
+
+        for mc in &mut self.monkeys {
+            mc.items.clear();
+        }
+        for ((monkey, item), counts) in journeys.into_iter().filter_map(Result::ok) {
+            self.monkeys[monkey].items.push(item);
+            for (mc, count) in self.monkeys.iter_mut().zip(counts) {
+                mc.items_inspected += count;
+            }
+        }
+        self.rounds_played += rounds;
+        Ok(self)
+    }
+
+    /// Where one item is after `rounds` more rounds, playing every one.
+    fn play_item(&self, mut state: (usize, W), rounds: usize) -> Result<Journey<W>, EvalError> {
+        let mut counts = vec![0; self.monkeys.len()];
+        for round in 0..rounds {
+            state = self.play_item_round(state, self.rounds_played + round + 1, &mut counts)?;
+        }
+        Ok((state, counts))
+    }
+
+    /// One item's part in a round. Thrown to a monkey later in the order, the
+    /// item is inspected again the same round; otherwise it waits for the
+    /// next.
+    fn play_item_round(
+        &self,
+        (mut monkey, mut item): (usize, W),
+        round: usize,
+        counts: &mut [u64],
+    ) -> Result<(usize, W), EvalError> {
+        loop {
+            counts[monkey] += 1;
+            let (receiver, new) = self.inspect(monkey, item, round, None)?;
+            item = new;
+            if receiver <= monkey {
+                return Ok((receiver, item));
+            }
+            monkey = receiver;
+        }
+    }
+
+    /// How many items each monkey has inspected so far.
+    pub fn activity(&self) -> Activity {
+        Activity {
+            round: self.rounds_played,
+            inspected: self.monkeys.iter().map(|m| m.items_inspected).collect(),
+        }
+    }
+
+    /// Plays up to `rounds` more rounds as it's iterated, yielding the
+    /// activity after each, or the error that ended the series.
+    pub fn activity_series(&mut self, rounds: usize) -> ActivitySeries<'_, W> {
+        ActivitySeries {
+            sim: self,
+            rounds_left: rounds,
+        }
+    }
+
+    /// The product of the two highest inspection counts, or `None` if it
+    /// doesn't fit in a `u64`.
+    pub fn monkey_business(&self) -> Option<u64> {
+        self.activity().business(2)
+    }
+}
+
+impl<W: Worry + Eq + Hash> Simulation<W> {
+    /// Gets to where [`run`](Self::run) would, without playing every round.
+    ///
+    /// An item's monkey and worry level at the start of a round decide the
+    /// rest of its journey, so once that state repeats the item's
+    /// inspections go round in a cycle and the remaining rounds are counted
+    /// rather than played. That needs relief which keeps worry bounded, like
+    /// [`Relief::Modular`]; otherwise this is no faster than `run`. See the
+    /// [module docs](self) for how following items differs from `run`.
+    pub fn fast_forward(&mut self, rounds: usize) -> Result<&mut Self, EvalError> {
+        if self.trace.is_some() {
+            return self.run(rounds);
+        }
+
+        let journeys = self
+            .held_items()
+            .into_iter()
+            .map(|item| self.follow_item(item, rounds))
+            .collect();
+        self.finish_journeys(journeys, rounds)
+    }
+
+    /// Where one item is after `rounds` more rounds, and how many times each
+    /// monkey inspected it on the way.
+    fn follow_item(&self, start: (usize, W), rounds: usize) -> Result<Journey<W>, EvalError> {
+        // The item's state at the start of each round, and how many times
+        // each monkey had inspected it by then.
+        let mut states = vec![start];
+        let mut counts = vec![vec![0; self.monkeys.len()]];
+        let mut seen = HashMap::new();
+
+        for round in 0..rounds {
+            let state = &states[round];
+            if let Some(&cycle_start) = seen.get(state) {
+                return Ok(extrapolate(&states, &counts, cycle_start, rounds));
+            }
+            seen.insert(state.clone(), round);
+
+            let mut round_counts = counts[round].clone();
+            let next = self.play_item_round(
+                state.clone(),
+                self.rounds_played + round + 1,
+                &mut round_counts,
+            )?;
+            states.push(next);
+            counts.push(round_counts);
+        }
+
+        Ok((states.swap_remove(rounds), counts.swap_remove(rounds)))
+    }
+}
+
+/// Where an item ended up, with how many times each monkey inspected it.
+type Journey<W> = ((usize, W), Vec<u64>);
+
+/// Where an item is after `rounds` rounds, given its state and counts at the
+/// start of rounds up to one whose state was first seen at `cycle_start`.
+fn extrapolate<W: Clone>(
+    states: &[(usize, W)],
+    counts: &[Vec<u64>],
+    cycle_start: usize,
+    rounds: usize,
+) -> Journey<W> {
+    let cycle_end = states.len() - 1;
+    let cycle_len = cycle_end - cycle_start;
+    let cycles = ((rounds - cycle_start) / cycle_len) as u64;
+    let offset = (rounds - cycle_start) % cycle_len;
+
+    let counts = (0..counts[0].len())
+        .map(|m| {
+            let per_cycle = counts[cycle_end][m] - counts[cycle_start][m];
+            let partial = counts[cycle_start + offset][m] - counts[cycle_start][m];
+            counts[cycle_start][m] + cycles * per_cycle + partial
+        })
+        .collect();
+    (states[cycle_start + offset].clone(), counts)
+}
+
+/// `None` if it doesn't fit in a `u64`. Neither `a` nor `b` may be zero.
+fn checked_lcm(a: u64, b: u64) -> Option<u64> {
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day11;
+    use aoc_core::solution::Solution;
+
+    fn example() -> Vec<Monkey> {
+        Day11::parse(include_str!("example.txt")).unwrap()
+    }
+
+    fn inspections<W: Worry>(sim: &Simulation<W>) -> Vec<u64> {
+        sim.monkeys().iter().map(|m| m.items_inspected).collect()
+    }
+
+    #[test]
+    fn divide_by_three_first_round() {
+        let mut sim: Simulation = Simulation::new(&example(), Relief::DivideByThree).unwrap();
+        sim.play_round().unwrap();
+
+        let items = sim
+            .monkeys()
+            .iter()
+            .map(|m| m.items.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            items,
+            [
+                vec![20, 23, 27, 26],
+                vec![2080, 25, 167, 207, 401, 1046],
+                vec![],
+                vec![],
+            ]
+        );
+    }
+
+    #[test]
+    fn modular_inspection_counts() {
+        let mut sim: Simulation = Simulation::new(&example(), Relief::Modular).unwrap();
+        assert_eq!(inspections(sim.run(1).unwrap()), [2, 4, 3, 6]);
+        assert_eq!(inspections(sim.run(19).unwrap()), [99, 97, 8, 103]);
+        assert_eq!(sim.rounds_played(), 20);
+    }
+
+    #[test]
+    fn custom_relief() {
+        let divisor_product = 23 * 19 * 13 * 17;
+        let relief = Relief::custom(move |w: u64| w % divisor_product);
+        let mut custom = Simulation::new(&example(), relief).unwrap();
+        let mut modular: Simulation = Simulation::new(&example(), Relief::Modular).unwrap();
+
+        assert_eq!(
+            custom.run(1000).unwrap().monkey_business(),
+            modular.run(1000).unwrap().monkey_business()
+        );
+    }
+
+    #[test]
+    fn modulus_only_needed_for_modular_relief() {
+        let mut monkeys = example();
+        for mc in &mut monkeys {
+            // Primes past 2^32, so any two of them overflow.
+            mc.divisor = 4_294_967_311;
+        }
+        monkeys[0].divisor = 4_294_967_357;
+        monkeys[1].divisor = 4_294_967_371;
+
+        assert!(matches!(
+            Simulation::<u64>::new(&monkeys, Relief::Modular),
+            Err(SetupError::ModulusOverflow)
+        ));
+        let mut sim: Simulation = Simulation::new(&monkeys, Relief::DivideByThree).unwrap();
+        sim.run(20).unwrap();
+
+        // Shared factors count once.
+        let mut monkeys = example();
+        for (mc, divisor) in monkeys.iter_mut().zip([6, 10, 15, 4]) {
+            mc.divisor = divisor;
+        }
+        let sim: Simulation = Simulation::new(&monkeys, Relief::Modular).unwrap();
+        assert_eq!(sim.modulus, 60);
+    }
+
+    #[test]
+    fn zero_divisor_is_an_error() {
+        let mut monkeys = example();
+        monkeys[2].divisor = 0;
+        for relief in [Relief::Modular, Relief::DivideByThree] {
+            assert!(matches!(
+                Simulation::<u64>::new(&monkeys, relief),
+                Err(SetupError::ZeroDivisor { monkey: 2 })
+            ));
+        }
+    }
+
+    #[test]
+    fn overflow_is_reported() {
+        let mut sim = Simulation::new(&example(), Relief::custom(|w: u64| w)).unwrap();
+        let err = sim.run(1000).unwrap_err();
+
+        assert_eq!(err.kind, ArithError::Overflow);
+        // The reported monkey and item are the ones that overflow.
+        let item = err.item.parse::<u64>().unwrap();
+        let operation = &example()[err.monkey].operation;
+        assert_eq!(operation.eval(&item), Err(ArithError::Overflow));
+    }
+
+    #[test]
+    fn failed_round_changes_nothing() {
+        let mut sim = Simulation::new(&example(), Relief::custom(|w: u64| w)).unwrap();
+        sim.start_trace();
+        let mut before = sim.monkeys().to_vec();
+        let mut recorded = 0;
+        let err = loop {
+            match sim.play_round() {
+                Ok(()) => {
+                    before = sim.monkeys().to_vec();
+                    recorded = sim.trace().unwrap().inspections().len();
+                }
+                Err(e) => break e,
+            }
+        };
+
+        assert_eq!(err.round, sim.rounds_played() + 1);
+        assert_eq!(sim.monkeys(), before);
+        assert_eq!(sim.trace().unwrap().inspections().len(), recorded);
+        // Every item is still where it was, tracked under its old id.
+        let held = sim.item_ids.iter().map(Vec::len).collect::<Vec<_>>();
+        let expected = before.iter().map(|m| m.items.len()).collect::<Vec<_>>();
+        assert_eq!(held, expected);
+        assert_eq!(sim.play_round().unwrap_err().round, err.round);
+    }
+
+    #[test]
+    fn trace_follows_items() {
+        let mut sim: Simulation = Simulation::new(&example(), Relief::DivideByThree).unwrap();
+        sim.start_trace();
+        sim.run(1).unwrap();
+        let trace = sim.trace().unwrap();
+
+        // Monkey 0's first item, 79, goes to monkey 3 as 500 and then to
+        // monkey 1 as 167.
+        let journey = trace
+            .item(0)
+            .map(|i| (i.monkey, i.before.as_str(), i.after.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(journey, [(0, "79", "500"), (3, "500", "167")]);
+        assert_eq!(trace.path(0), [0, 3, 1]);
+
+        // Every inspection is traced.
+        let inspected = sim.monkeys().iter().map(|m| m.items_inspected).sum::<u64>();
+        assert_eq!(trace.inspections().len() as u64, inspected);
+    }
+
+    #[test]
+    fn trace_exports() {
+        let mut sim: Simulation = Simulation::new(&example(), Relief::DivideByThree).unwrap();
+        sim.start_trace();
+        sim.run(1).unwrap();
+        let trace = sim.trace().unwrap();
+
+        let mut csv = vec![];
+        trace.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("item,round,monkey,before,after,thrown_to")
+        );
+        assert_eq!(lines.next(), Some("0,1,0,79,500,3"));
+
+        let mut json = vec![];
+        trace.write_json(&mut json).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), trace.inspections().len());
+        assert_eq!(json[0]["after"], "500");
+    }
+
+    fn assert_same_state(a: &Simulation, b: &Simulation) {
+        assert_eq!(inspections(a), inspections(b));
+        for (a, b) in a.monkeys().iter().zip(b.monkeys()) {
+            let mut a = a.items.clone();
+            let mut b = b.items.clone();
+            a.sort();
+            b.sort();
+            assert_eq!(a, b);
+        }
+        assert_eq!(a.rounds_played(), b.rounds_played());
+    }
+
+    #[test]
+    fn fast_forward_matches_brute_force() {
+        for rounds in 0..=60 {
+            let mut played: Simulation = Simulation::new(&example(), Relief::Modular).unwrap();
+            let mut skipped: Simulation = Simulation::new(&example(), Relief::Modular).unwrap();
+            played.run(rounds).unwrap();
+            skipped.fast_forward(rounds).unwrap();
+            assert_same_state(&played, &skipped);
+        }
+    }
+
+    #[test]
+    fn fast_forward_without_cycles() {
+        let mut played: Simulation = Simulation::new(&example(), Relief::DivideByThree).unwrap();
+        let mut skipped: Simulation = Simulation::new(&example(), Relief::DivideByThree).unwrap();
+        played.run(20).unwrap();
+        skipped.fast_forward(20).unwrap();
+        assert_same_state(&played, &skipped);
+    }
+
+    #[test]
+    fn fast_forward_in_steps() {
+        let mut played: Simulation = Simulation::new(&example(), Relief::Modular).unwrap();
+        let mut skipped: Simulation = Simulation::new(&example(), Relief::Modular).unwrap();
+        played.run(1000).unwrap();
+        skipped
+            .fast_forward(300)
+            .unwrap()
+            .fast_forward(700)
+            .unwrap();
+        assert_same_state(&played, &skipped);
+    }
+
+    #[test]
+    fn fast_forward_millions_of_rounds() {
+        let mut sim: Simulation = Simulation::new(&example(), Relief::Modular).unwrap();
+        assert_eq!(
+            sim.fast_forward(10_000).unwrap().monkey_business(),
+            Some(2713310158)
+        );
+
+        let mut sim: Simulation = Simulation::new(&example(), Relief::Modular).unwrap();
+        sim.fast_forward(10_000_000).unwrap();
+        assert_eq!(sim.rounds_played(), 10_000_000);
+        let total = inspections(&sim).iter().sum::<u64>();
+        // Every item is inspected at least once a round.
+        assert!(total >= 10 * 10_000_000);
+    }
+
+    #[test]
+    fn parallel_matches_serial() {
+        let reliefs: [fn() -> Relief; 2] = [|| Relief::Modular, || Relief::DivideByThree];
+        for relief in reliefs {
+            let mut serial = Simulation::new(&example(), relief()).unwrap();
+            let mut parallel = Simulation::new(&example(), relief()).unwrap();
+            for rounds in [0, 1, 19] {
+                serial.run(rounds).unwrap();
+                parallel.run_parallel(rounds).unwrap();
+                assert_same_state(&serial, &parallel);
+            }
+        }
+
+        let mut sim: Simulation = Simulation::new(&example(), Relief::Modular).unwrap();
+        assert_eq!(
+            sim.run_parallel(10_000).unwrap().monkey_business(),
+            Some(2713310158)
+        );
+    }
+
+    #[test]
+    fn parallel_reports_the_same_error() {
+        let mut serial = Simulation::new(&example(), Relief::custom(|w: u64| w)).unwrap();
+        let mut parallel = Simulation::new(&example(), Relief::custom(|w: u64| w)).unwrap();
+        let expected = serial.run(1000).unwrap_err();
+        let err = parallel.run_parallel(1000).unwrap_err();
+
+        assert_eq!((err.round, err.monkey), (expected.round, expected.monkey));
+        assert_eq!(parallel.rounds_played(), 0);
+    }
+
+    #[test]
+    fn narration_matches_the_puzzle() {
+        let mut sim: Simulation = Simulation::new(&example(), Relief::DivideByThree).unwrap();
+        let narration = sim.narrate_round().unwrap() + &sim.holdings();
+        pretty_assertions::assert_eq!(narration, include_str!("round1.txt"));
+
+        let mut sim: Simulation = Simulation::new(&example(), Relief::Modular).unwrap();
+        sim.run(20).unwrap();
+        assert_eq!(
+            sim.inspection_counts(),
+            "== After round 20 ==\n\
+             Monkey 0 inspected items 99 times.\n\
+             Monkey 1 inspected items 97 times.\n\
+             Monkey 2 inspected items 8 times.\n\
+             Monkey 3 inspected items 103 times.\n"
+        );
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn bigint_runs_unreduced() {
+        use num_bigint::BigUint;
+
+        let mut exact = Simulation::new(&example(), Relief::custom(|w: BigUint| w)).unwrap();
+        let mut modular: Simulation = Simulation::new(&example(), Relief::Modular).unwrap();
+
+        assert_eq!(
+            inspections(exact.run(20).unwrap()),
+            inspections(modular.run(20).unwrap())
+        );
+    }
+}
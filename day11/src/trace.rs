@@ -0,0 +1,70 @@
+use std::io;
+
+use miette::IntoDiagnostic;
+use serde::Serialize;
+
+/// One inspection of one item. Items are numbered in the order monkeys held
+/// them when tracing started, monkey by monkey.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Inspection {
+    pub item: usize,
+    pub round: usize,
+    pub monkey: usize,
+    /// Worry levels are kept as text so that any [`Worry`](crate::Worry) type
+    /// can be traced.
+    pub before: String,
+    /// After the operation and relief, as thrown.
+    pub after: String,
+    pub thrown_to: usize,
+}
+
+/// Every inspection since tracing started, in the order they happened.
+#[derive(Debug, Clone, Default)]
+pub struct Trace {
+    inspections: Vec<Inspection>,
+}
+
+impl Trace {
+    pub(crate) fn record(&mut self, inspection: Inspection) {
+        self.inspections.push(inspection);
+    }
+
+    /// Forgets every inspection after the first `len`.
+    pub(crate) fn truncate(&mut self, len: usize) {
+        self.inspections.truncate(len);
+    }
+
+    pub fn inspections(&self) -> &[Inspection] {
+        &self.inspections
+    }
+
+    /// One item's journey from monkey to monkey.
+    pub fn item(&self, item: usize) -> impl Iterator<Item = &Inspection> + '_ {
+        self.inspections.iter().filter(move |i| i.item == item)
+    }
+
+    /// The monkeys `item` has visited, in order, starting with the one that
+    /// held it when tracing started.
+    pub fn path(&self, item: usize) -> Vec<usize> {
+        let mut inspections = self.item(item).peekable();
+        let first = inspections.peek().map(|i| i.monkey);
+        first
+            .into_iter()
+            .chain(inspections.map(|i| i.thrown_to))
+            .collect()
+    }
+
+    /// Writes the inspections as a JSON array of objects.
+    pub fn write_json(&self, w: impl io::Write) -> miette::Result<()> {
+        serde_json::to_writer_pretty(w, &self.inspections).into_diagnostic()
+    }
+
+    /// Writes the inspections as CSV, with a header row.
+    pub fn write_csv(&self, w: impl io::Write) -> miette::Result<()> {
+        let mut writer = csv::Writer::from_writer(w);
+        for inspection in &self.inspections {
+            writer.serialize(inspection).into_diagnostic()?;
+        }
+        writer.flush().into_diagnostic()
+    }
+}
@@ -0,0 +1,39 @@
+use aoc_core::parse::BadInput;
+use miette::{LabeledSpan, SourceSpan};
+
+use crate::parse::{Monkey, MonkeySpans};
+
+/// Checks that parsed monkeys can actually play: they're numbered 0, 1, 2...
+/// in order, only throw to monkeys that exist and aren't themselves, and
+/// never test divisibility by zero. Every problem gets a label of its own.
+pub fn validate(src: &str, monkeys: &[(Monkey, MonkeySpans)]) -> Result<(), BadInput> {
+    let mut labels = vec![];
+    let mut label = |span: SourceSpan, text: String| {
+        labels.push(LabeledSpan::new_with_span(Some(text), span));
+    };
+
+    for (index, (monkey, spans)) in monkeys.iter().enumerate() {
+        if monkey.id != index {
+            label(spans.id, format!("expected monkey {index} here"));
+        }
+        if monkey.divisor == 0 {
+            label(spans.divisor, "can't test divisibility by zero".to_owned());
+        }
+        for (receiver, span) in [
+            (monkey.receiver_if_true, spans.receiver_if_true),
+            (monkey.receiver_if_false, spans.receiver_if_false),
+        ] {
+            if receiver >= monkeys.len() {
+                label(span, format!("there's no monkey {receiver}"));
+            } else if receiver == index {
+                label(span, format!("monkey {index} would throw to itself"));
+            }
+        }
+    }
+
+    if labels.is_empty() {
+        Ok(())
+    } else {
+        Err(BadInput::with_labels(src, labels))
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt;
+
+use crate::parse::BinOp;
+
+/// Why an operation couldn't produce a worry level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum ArithError {
+    #[error("overflowed")]
+    Overflow,
+    #[error("went below zero")]
+    Underflow,
+    #[error("divided by zero")]
+    DivisionByZero,
+}
+
+/// A worry level. `u64` is fast but can overflow when nothing keeps worry in
+/// check; with the `bigint` feature, `BigUint` never does.
+pub trait Worry: Clone + fmt::Debug + fmt::Display + Send + Sync {
+    fn from_u64(n: u64) -> Self;
+
+    fn apply(op: BinOp, l: &Self, r: &Self) -> Result<Self, ArithError>;
+
+    fn div_u64(&self, d: u64) -> Self;
+
+    fn rem_u64(&self, m: u64) -> Self;
+
+    fn is_multiple_of(&self, d: u64) -> bool;
+}
+
+impl Worry for u64 {
+    fn from_u64(n: u64) -> Self {
+        n
+    }
+
+    fn apply(op: BinOp, &l: &Self, &r: &Self) -> Result<Self, ArithError> {
+        match op {
+            BinOp::Add => l.checked_add(r).ok_or(ArithError::Overflow),
+            BinOp::Sub => l.checked_sub(r).ok_or(ArithError::Underflow),
+            BinOp::Mul => l.checked_mul(r).ok_or(ArithError::Overflow),
+            BinOp::Div => l.checked_div(r).ok_or(ArithError::DivisionByZero),
+            BinOp::Pow => match l {
+                0 | 1 if r > 0 => Ok(l),
+                _ => u32::try_from(r)
+                    .ok()
+                    .and_then(|r| l.checked_pow(r))
+                    .ok_or(ArithError::Overflow),
+            },
+        }
+    }
+
+    fn div_u64(&self, d: u64) -> Self {
+        self / d
+    }
+
+    fn rem_u64(&self, m: u64) -> Self {
+        self % m
+    }
+
+    fn is_multiple_of(&self, d: u64) -> bool {
+        u64::is_multiple_of(*self, d)
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl Worry for num_bigint::BigUint {
+    fn from_u64(n: u64) -> Self {
+        n.into()
+    }
+
+    fn apply(op: BinOp, l: &Self, r: &Self) -> Result<Self, ArithError> {
+        use num_bigint::BigUint;
+
+        match op {
+            BinOp::Add => Ok(l + r),
+            BinOp::Sub if l < r => Err(ArithError::Underflow),
+            BinOp::Sub => Ok(l - r),
+            BinOp::Mul => Ok(l * r),
+            BinOp::Div if *r == BigUint::default() => Err(ArithError::DivisionByZero),
+            BinOp::Div => Ok(l / r),
+            // Exponents beyond `u32::MAX` would need more memory than there is,
+            // except for these.
+            BinOp::Pow if *l <= BigUint::from(1_u8) && *r > BigUint::default() => Ok(l.clone()),
+            BinOp::Pow => u32::try_from(r)
+                .map(|r| l.pow(r))
+                .map_err(|_| ArithError::Overflow),
+        }
+    }
+
+    fn div_u64(&self, d: u64) -> Self {
+        self / d
+    }
+
+    fn rem_u64(&self, m: u64) -> Self {
+        self % m
+    }
+
+    fn is_multiple_of(&self, d: u64) -> bool {
+        if d == 0 {
+            *self == Self::default()
+        } else {
+            (self % d) == Self::default()
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use std::str::FromStr;
+
+use aoc_core::{parse::try_parse_lines, solution::Solution};
+
+#[derive(Debug, Clone, Copy)]
+enum Move {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Move {
+    fn inherent_points(self) -> usize {
+        match self {
+            Move::Rock => 1,
+            Move::Paper => 2,
+            Move::Scissors => 3,
+        }
+    }
+
+    fn beats(self, other: Move) -> bool {
+        matches!(
+            (self, other),
+            (Self::Rock, Self::Scissors)
+                | (Self::Paper, Self::Rock)
+                | (Self::Scissors, Self::Paper)
+        )
+    }
+
+    fn outcome(self, theirs: Move) -> Outcome {
+        if self.beats(theirs) {
+            Outcome::Win
+        } else if theirs.beats(self) {
+            Outcome::Loss
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    const ALL_MOVES: [Move; 3] = [Move::Rock, Move::Paper, Move::Scissors];
+
+    fn winning_move(self) -> Move {
+        Self::ALL_MOVES
+            .iter()
+            .copied()
+            .find(|m| m.beats(self))
+            .expect("At least one move beats this move.")
+    }
+
+    fn losing_move(self) -> Move {
+        Self::ALL_MOVES
+            .iter()
+            .copied()
+            .find(|&m| self.beats(m))
+            .expect("This move beats at least one other move.")
+    }
+
+    fn drawing_move(self) -> Move {
+        self
+    }
+}
+
+impl TryFrom<char> for Move {
+    type Error = miette::Report;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'A' => Ok(Move::Rock),
+            'B' => Ok(Move::Paper),
+            'C' => Ok(Move::Scissors),
+            _ => Err(miette::miette!("Not a valid move: {c:?}")),
+        }
+    }
+}
+
+/// The second column of the strategy guide, whose meaning differs between
+/// the two parts.
+#[derive(Debug, Clone, Copy)]
+enum Column {
+    X,
+    Y,
+    Z,
+}
+
+impl TryFrom<char> for Column {
+    type Error = miette::Report;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'X' => Ok(Column::X),
+            'Y' => Ok(Column::Y),
+            'Z' => Ok(Column::Z),
+            _ => Err(miette::miette!("Not a valid second column: {c:?}")),
+        }
+    }
+}
+
+impl From<Column> for Move {
+    fn from(c: Column) -> Self {
+        match c {
+            Column::X => Move::Rock,
+            Column::Y => Move::Paper,
+            Column::Z => Move::Scissors,
+        }
+    }
+}
+
+impl From<Column> for Outcome {
+    fn from(c: Column) -> Self {
+        match c {
+            Column::X => Outcome::Loss,
+            Column::Y => Outcome::Draw,
+            Column::Z => Outcome::Win,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GuideLine {
+    theirs: Move,
+    second: Column,
+}
+
+impl FromStr for GuideLine {
+    type Err = miette::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let (Some(theirs), Some(' '), Some(second), None) =
+            (chars.next(), chars.next(), chars.next(), chars.next())
+        else {
+            return Err(miette::miette!("Expected <theirs>SP<second>EOF, got {s:?}"));
+        };
+
+        let theirs = Move::try_from(theirs)?;
+        let second = Column::try_from(second)?;
+
+        Ok(Self { theirs, second })
+    }
+}
+
+impl GuideLine {
+    /// Part one: the second column is the move we play.
+    fn as_moves(self) -> Round {
+        Round {
+            theirs: self.theirs,
+            ours: self.second.into(),
+        }
+    }
+
+    /// Part two: the second column is how the round needs to end.
+    fn as_outcome(self) -> Round {
+        let outcome = Outcome::from(self.second);
+        Round {
+            theirs: self.theirs,
+            ours: outcome.matching_move(self.theirs),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Round {
+    theirs: Move,
+    ours: Move,
+}
+
+impl Round {
+    fn outcome(self) -> Outcome {
+        self.ours.outcome(self.theirs)
+    }
+
+    fn our_score(self) -> usize {
+        self.ours.inherent_points() + self.outcome().inherent_points()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Outcome {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl Outcome {
+    fn inherent_points(self) -> usize {
+        match self {
+            Outcome::Win => 6,
+            Outcome::Draw => 3,
+            Outcome::Loss => 0,
+        }
+    }
+
+    fn matching_move(self, theirs: Move) -> Move {
+        match self {
+            Outcome::Win => theirs.winning_move(),
+            Outcome::Draw => theirs.drawing_move(),
+            Outcome::Loss => theirs.losing_move(),
+        }
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<GuideLine>;
+    type AnswerOne = usize;
+    type AnswerTwo = usize;
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        Ok(try_parse_lines(input, GuideLine::from_str).collect::<Result<_, _>>()?)
+    }
+
+    fn part_one(guide: &Self::Input) -> miette::Result<usize> {
+        Ok(guide.iter().map(|line| line.as_moves().our_score()).sum())
+    }
+
+    fn part_two(guide: &Self::Input) -> miette::Result<usize> {
+        Ok(guide.iter().map(|line| line.as_outcome().our_score()).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part_one_example() {
+        let input = Day2::parse(EXAMPLE).unwrap();
+        assert_eq!(Day2::part_one(&input).unwrap(), 15);
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day2::parse(EXAMPLE).unwrap();
+        assert_eq!(Day2::part_two(&input).unwrap(), 12);
+    }
+}
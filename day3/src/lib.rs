@@ -0,0 +1,136 @@
+use aoc_core::{parse::try_parse_lines, solution::Solution};
+
+mod item {
+    #[repr(transparent)]
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Item(u8);
+
+    impl TryFrom<u8> for Item {
+        type Error = miette::Report;
+
+        fn try_from(value: u8) -> Result<Self, Self::Error> {
+            match value {
+                b'a'..=b'z' | b'A'..=b'Z' => Ok(Item(value)),
+                _ => Err(miette::miette!("Invalid item: `{}`", value as char)),
+            }
+        }
+    }
+
+    impl std::fmt::Debug for Item {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.0 as char)
+        }
+    }
+
+    impl Item {
+        pub(crate) fn priority(self) -> usize {
+            match self {
+                Item(b'a'..=b'z') => 1 + (self.0 - b'a') as usize,
+                Item(b'A'..=b'Z') => 27 + (self.0 - b'A') as usize,
+                _ => unreachable!(),
+            }
+        }
+    }
+}
+
+pub use item::Item;
+use std::collections::HashSet;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = Vec<Vec<Item>>;
+    type AnswerOne = usize;
+    type AnswerTwo = usize;
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        Ok(try_parse_lines(input, |line| {
+            line.bytes()
+                .map(Item::try_from)
+                .collect::<miette::Result<_>>()
+        })
+        .collect::<Result<_, _>>()?)
+    }
+
+    fn part_one(rucksacks: &Self::Input) -> miette::Result<usize> {
+        rucksacks
+            .iter()
+            .map(|rucksack| -> miette::Result<_> {
+                let (first, second) = rucksack.split_at(rucksack.len() / 2);
+
+                let first_items = first.iter().copied().collect::<HashSet<_>>();
+
+                second
+                    .iter()
+                    .find(|item| first_items.contains(item))
+                    .map(|item| item.priority())
+                    .ok_or_else(|| miette::miette!("compartments have no items in common"))
+            })
+            .sum::<miette::Result<usize>>()
+    }
+
+    fn part_two(rucksacks: &Self::Input) -> miette::Result<usize> {
+        let groups = rucksacks.chunks_exact(3);
+        if !groups.remainder().is_empty() {
+            return Err(miette::miette!(
+                "{} rucksacks don't make groups of three",
+                rucksacks.len()
+            ));
+        }
+
+        groups
+            .enumerate()
+            .map(|(group, rucksacks)| {
+                let [a, b, c] =
+                    [0, 1, 2].map(|i| rucksacks[i].iter().copied().collect::<HashSet<_>>());
+                a.iter()
+                    .copied()
+                    .find(|i| b.contains(i) && c.contains(i))
+                    .map(|i| i.priority())
+                    .ok_or_else(|| {
+                        miette::miette!(
+                            "group {} (lines {} to {}) has no badge in common",
+                            group + 1,
+                            3 * group + 1,
+                            3 * group + 3
+                        )
+                    })
+            })
+            .sum::<miette::Result<usize>>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part_one_example() {
+        let input = Day3::parse(EXAMPLE).unwrap();
+        assert_eq!(Day3::part_one(&input).unwrap(), 157);
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day3::parse(EXAMPLE).unwrap();
+        assert_eq!(Day3::part_two(&input).unwrap(), 70);
+    }
+
+    #[test]
+    fn part_two_bad_groups() {
+        let input = Day3::parse("ab\ncd\nef\nab").unwrap();
+        let err = Day3::part_two(&input).unwrap_err();
+        assert_eq!(err.to_string(), "4 rucksacks don't make groups of three");
+
+        let input = Day3::parse("ab\nbc\nca").unwrap();
+        let err = Day3::part_two(&input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "group 1 (lines 1 to 3) has no badge in common"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use aoc_core::{
+    parse::{parse_lines, ParseResult},
+    range::InclusiveRangeExt,
+    solution::Solution,
+};
+use nom::{character::complete as cc, combinator::map, sequence::separated_pair};
+use nom_supreme::tag::complete::tag;
+use std::ops::RangeInclusive;
+
+pub type Assignment = (RangeInclusive<u32>, RangeInclusive<u32>);
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = Vec<Assignment>;
+    type AnswerOne = usize;
+    type AnswerTwo = usize;
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        Ok(parse_lines(input, parse_assignment).collect::<Result<_, _>>()?)
+    }
+
+    fn part_one(pairs: &Self::Input) -> miette::Result<usize> {
+        let completely_redundant = pairs
+            .iter()
+            .filter(|(a, b)| a.completely_overlaps(b))
+            .count();
+
+        Ok(completely_redundant)
+    }
+
+    fn part_two(pairs: &Self::Input) -> miette::Result<usize> {
+        let partially_redundant = pairs.iter().filter(|(a, b)| a.overlaps(b)).count();
+
+        Ok(partially_redundant)
+    }
+}
+
+fn parse_range(i: &str) -> ParseResult<'_, RangeInclusive<u32>> {
+    map(
+        separated_pair(cc::u32, tag("-"), cc::u32),
+        |(start, end)| start..=end,
+    )(i)
+}
+
+fn parse_assignment(i: &str) -> ParseResult<'_, Assignment> {
+    separated_pair(parse_range, tag(","), parse_range)(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part_one_example() {
+        let input = Day4::parse(EXAMPLE).unwrap();
+        assert_eq!(Day4::part_one(&input).unwrap(), 2);
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day4::parse(EXAMPLE).unwrap();
+        assert_eq!(Day4::part_two(&input).unwrap(), 4);
+    }
+
+    #[test]
+    fn overlap_either_way_round() {
+        for pair in ["3-7,2-8", "2-8,3-7", "1-3,3-5", "3-5,1-3"] {
+            let input = Day4::parse(pair).unwrap();
+            assert_eq!(Day4::part_two(&input).unwrap(), 1, "{pair}");
+        }
+        let input = Day4::parse("1-2,3-4\n3-4,1-2").unwrap();
+        assert_eq!(Day4::part_two(&input).unwrap(), 0);
+    }
+
+    #[test]
+    fn malformed_range_is_an_error() {
+        assert!(Day4::parse("2-4,6-8\n2-3,4x5\n").is_err());
+    }
+}
@@ -0,0 +1,254 @@
+use nom::{
+    branch::alt,
+    character::complete::{anychar, line_ending, multispace0, space0, space1},
+    combinator::{cut, map, map_opt, opt},
+    multi::separated_list1,
+    sequence::{delimited, preceded, tuple},
+};
+use nom_supreme::tag::complete::tag;
+
+use aoc_core::{
+    parse::{parse_all, parse_number, ParseResult},
+    solution::Solution,
+};
+use itertools::Itertools;
+use std::fmt;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input = Procedure;
+    type AnswerOne = String;
+    type AnswerTwo = String;
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        Ok(parse_all(input, parse_procedure)?)
+    }
+
+    fn part_one(procedure: &Self::Input) -> miette::Result<String> {
+        let mut piles = procedure.piles.clone();
+        for &ins in &procedure.instructions {
+            piles.apply(ins)?;
+        }
+
+        Ok(piles.top_crates())
+    }
+
+    fn part_two(procedure: &Self::Input) -> miette::Result<String> {
+        let mut piles = procedure.piles.clone();
+        for &ins in &procedure.instructions {
+            piles.apply_once(ins)?;
+        }
+
+        Ok(piles.top_crates())
+    }
+}
+
+/// The starting piles and the rearrangement to apply to them.
+pub struct Procedure {
+    piles: Piles,
+    instructions: Vec<Instruction>,
+}
+
+#[derive(Clone, Copy)]
+struct Crate(char);
+
+impl fmt::Debug for Crate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone)]
+struct Piles(Vec<Vec<Crate>>);
+
+impl fmt::Debug for Piles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, pile) in self.0.iter().enumerate() {
+            writeln!(f, "Pile {i}: {pile:?}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Crate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl Piles {
+    /// Empty piles have no top crate and are left out.
+    fn top_crates(&self) -> String {
+        self.0.iter().filter_map(|pile| pile.last()).join("")
+    }
+
+    fn pile_mut(&mut self, id: usize) -> miette::Result<&mut Vec<Crate>> {
+        self.0
+            .get_mut(id)
+            .ok_or_else(|| miette::miette!("there is no pile {}", id + 1))
+    }
+
+    fn take(&mut self, id: usize, quantity: usize) -> miette::Result<Vec<Crate>> {
+        let pile = self.pile_mut(id)?;
+        let at = pile.len().checked_sub(quantity).ok_or_else(|| {
+            miette::miette!(
+                "cannot move {quantity} crates off pile {}, which holds {}",
+                id + 1,
+                pile.len()
+            )
+        })?;
+        Ok(pile.split_off(at))
+    }
+
+    fn apply(&mut self, ins: Instruction) -> miette::Result<()> {
+        let moved = self.take(ins.src, ins.quantity)?;
+        self.pile_mut(ins.dst)?.extend(moved.into_iter().rev());
+        Ok(())
+    }
+
+    fn apply_once(&mut self, ins: Instruction) -> miette::Result<()> {
+        let moved = self.take(ins.src, ins.quantity)?;
+        self.pile_mut(ins.dst)?.extend(moved);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Instruction {
+    quantity: usize,
+    src: usize,
+    dst: usize,
+}
+
+fn parse_procedure(i: &str) -> ParseResult<'_, Procedure> {
+    let (i, crate_lines) = separated_list1(line_ending, parse_crate_line)(i)?;
+    let (i, _) = tuple((line_ending, parse_pile_numbers, line_ending, line_ending))(i)?;
+    let (i, instructions) = separated_list1(line_ending, parse_instruction)(i)?;
+    let (i, _) = multispace0(i)?;
+
+    let procedure = Procedure {
+        piles: Piles(transpose_reverse(crate_lines)),
+        instructions,
+    };
+    Ok((i, procedure))
+}
+
+fn parse_crate(i: &str) -> ParseResult<'_, Crate> {
+    let f = delimited(tag("["), anychar, tag("]"));
+    map(f, Crate)(i)
+}
+
+fn parse_hole(i: &str) -> ParseResult<'_, ()> {
+    map(tag("   "), drop)(i)
+}
+
+fn parse_crate_or_hole(i: &str) -> ParseResult<'_, Option<Crate>> {
+    alt((map(parse_crate, Some), map(parse_hole, |_| None)))(i)
+}
+
+fn parse_crate_line(i: &str) -> ParseResult<'_, Vec<Option<Crate>>> {
+    let (mut i, c) = parse_crate_or_hole(i)?;
+    let mut v = vec![c];
+
+    loop {
+        let (next_i, maybe_c) = opt(preceded(tag(" "), parse_crate_or_hole))(i)?;
+        match maybe_c {
+            Some(c) => v.push(c),
+            None => break,
+        }
+        i = next_i;
+    }
+
+    Ok((i, v))
+}
+
+/// The line of pile numbers under the crates; they're always `1` to `n`, so
+/// only their layout is checked.
+fn parse_pile_numbers(i: &str) -> ParseResult<'_, ()> {
+    map(
+        tuple((space0, separated_list1(space1, parse_number), space0)),
+        drop,
+    )(i)
+}
+
+fn parse_pile_id(i: &str) -> ParseResult<'_, usize> {
+    map_opt(parse_number, |i| i.checked_sub(1))(i)
+}
+
+/// Once a line starts with `move `, it has to be an instruction: the list of
+/// them mustn't end quietly before a typo.
+fn parse_instruction(i: &str) -> ParseResult<'_, Instruction> {
+    map(
+        preceded(
+            tag("move "),
+            cut(tuple((
+                parse_number,
+                preceded(tag(" from "), parse_pile_id),
+                preceded(tag(" to "), parse_pile_id),
+            ))),
+        ),
+        |(quantity, src, dst)| Instruction { quantity, src, dst },
+    )(i)
+}
+
+/// Turns rows of crate slots, top row first, into piles listed bottom crate
+/// first. Rows may be shorter than the widest one when trailing holes were
+/// trimmed.
+fn transpose_reverse<T>(v: Vec<Vec<Option<T>>>) -> Vec<Vec<T>> {
+    let len = v.iter().map(Vec::len).max().unwrap_or_default();
+    let mut iters: Vec<_> = v.into_iter().map(|n| n.into_iter()).collect();
+
+    (0..len)
+        .map(|_| {
+            iters
+                .iter_mut()
+                .rev()
+                .filter_map(|n| n.next().flatten())
+                .collect::<Vec<T>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part_one_example() {
+        let input = Day5::parse(EXAMPLE).unwrap();
+        assert_eq!(Day5::part_one(&input).unwrap(), "CMZ");
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day5::parse(EXAMPLE).unwrap();
+        assert_eq!(Day5::part_two(&input).unwrap(), "MCD");
+    }
+
+    #[test]
+    fn moving_more_crates_than_a_pile_holds_is_an_error() {
+        let example = EXAMPLE.replace("move 3 from 1 to 3", "move 4 from 1 to 3");
+        let input = Day5::parse(&example).unwrap();
+        assert!(Day5::part_one(&input).is_err());
+        assert!(Day5::part_two(&input).is_err());
+    }
+
+    #[test]
+    fn typo_in_an_instruction_names_what_was_expected() {
+        let example = EXAMPLE.replace("move 3 from 1 to 3", "move 3 frm 1 to 3");
+        let err = parse_all(&example, parse_procedure)
+            .map(|_| ())
+            .unwrap_err();
+        let labels = miette::Diagnostic::labels(&err)
+            .unwrap()
+            .map(|l| (l.label().unwrap().to_owned(), l.offset()))
+            .collect::<Vec<_>>();
+        let at = example.find("frm").unwrap() - 1;
+        assert_eq!(labels, [("expected \" from \"".to_owned(), at)]);
+    }
+}
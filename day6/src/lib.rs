@@ -0,0 +1,87 @@
+use aoc_core::solution::Solution;
+use std::collections::HashSet;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Input = String;
+    type AnswerOne = usize;
+    type AnswerTwo = usize;
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        Ok(input.trim_end().to_owned())
+    }
+
+    fn part_one(datastream: &Self::Input) -> miette::Result<usize> {
+        find_marker(datastream, 4)
+    }
+
+    fn part_two(datastream: &Self::Input) -> miette::Result<usize> {
+        find_marker(datastream, 14)
+    }
+}
+
+fn find_marker(datastream: &str, length: usize) -> miette::Result<usize> {
+    datastream
+        .find_marker_of_len(length)
+        .ok_or_else(|| miette::miette!("no marker of length {length} in datastream"))
+}
+
+trait ElfDevice {
+    fn find_marker_of_len(&self, length: usize) -> Option<usize>;
+}
+
+impl ElfDevice for &str {
+    fn find_marker_of_len(&self, length: usize) -> Option<usize> {
+        self.as_bytes()
+            .windows(length)
+            .position(|window| window.iter().collect::<HashSet<_>>().len() == length)
+            .map(|pos| pos + length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Day6, ElfDevice};
+    use aoc_core::solution::Solution;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn test_find_packet() {
+        assert_eq!(
+            Some(7),
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb".find_marker_of_len(4)
+        );
+    }
+
+    #[test]
+    fn part_one_examples() {
+        for (datastream, expected) in [
+            (EXAMPLE, 7),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11),
+        ] {
+            let input = Day6::parse(datastream).unwrap();
+            assert_eq!(Day6::part_one(&input).unwrap(), expected, "{datastream}");
+        }
+    }
+
+    #[test]
+    fn part_two_examples() {
+        for (datastream, expected) in [
+            (EXAMPLE, 19),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 26),
+        ] {
+            let input = Day6::parse(datastream).unwrap();
+            assert_eq!(Day6::part_two(&input).unwrap(), expected, "{datastream}");
+        }
+    }
+}
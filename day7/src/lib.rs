@@ -0,0 +1,227 @@
+use aoc_core::{
+    parse::{parse_lines, BadInput, ParseResult},
+    solution::Solution,
+};
+use camino::Utf8PathBuf;
+use id_tree::{InsertBehavior, Node, NodeId, Tree};
+use miette::IntoDiagnostic;
+use nom::{
+    branch::alt,
+    bytes::complete::take_while1,
+    combinator::{map, value},
+    sequence::{preceded, separated_pair},
+};
+use nom_supreme::tag::complete::tag;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Input = Tree<FsEntry>;
+    type AnswerOne = u64;
+    type AnswerTwo = u64;
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        build_tree(input)
+    }
+
+    fn part_one(tree: &Self::Input) -> miette::Result<u64> {
+        let sizes = dir_sizes(tree)?;
+        Ok(sizes.into_iter().filter(|&size| size <= 100_000).sum())
+    }
+
+    fn part_two(tree: &Self::Input) -> miette::Result<u64> {
+        let total_space = 70_000_000_u64;
+        let used_space = total_size(tree, tree.get(root(tree)?).into_diagnostic()?)?;
+        let free_space = total_space
+            .checked_sub(used_space)
+            .ok_or_else(|| miette::miette!("{used_space} used is more than the disk holds"))?;
+        let needed_space = 30_000_000_u64;
+        let space_to_free = needed_space
+            .checked_sub(free_space)
+            .ok_or_else(|| miette::miette!("{free_space} is already free, nothing to delete"))?;
+
+        let remove_size = dir_sizes(tree)?
+            .into_iter()
+            .filter(|&size| size >= space_to_free)
+            .min()
+            .ok_or_else(|| miette::miette!("no directory is big enough to free {space_to_free}"))?;
+
+        Ok(remove_size)
+    }
+}
+
+fn build_tree(input: &str) -> miette::Result<Tree<FsEntry>> {
+    let lines = input.lines().zip(parse_lines(input, parse_line));
+
+    let mut tree = Tree::<FsEntry>::new();
+    let root = tree
+        .insert(Node::new(FsEntry { size: 0 }), InsertBehavior::AsRoot)
+        .into_diagnostic()?;
+    let mut curr = root;
+
+    for (text, line) in lines {
+        match line? {
+            Line::Command(cmd) => match cmd {
+                Command::Ls => {
+                    // No action
+                }
+                Command::Cd(path) => match path.as_str() {
+                    "/" => {
+                        // No action: only occurs at start of input
+                    }
+                    ".." => {
+                        curr = tree
+                            .get(&curr)
+                            .into_diagnostic()?
+                            .parent()
+                            .ok_or_else(|| BadInput::at(input, text, "already at the root"))?
+                            .clone();
+                    }
+                    _ => {
+                        let node = Node::new(FsEntry { size: 0 });
+                        curr = tree
+                            .insert(node, InsertBehavior::UnderNode(&curr))
+                            .into_diagnostic()?;
+                    }
+                },
+            },
+            Line::Entry(entry) => match entry {
+                Entry::Dir => {
+                    // No action
+                }
+                Entry::File(size) => {
+                    let node = Node::new(FsEntry { size });
+                    tree.insert(node, InsertBehavior::UnderNode(&curr))
+                        .into_diagnostic()?;
+                }
+            },
+        }
+    }
+
+    Ok(tree)
+}
+
+fn parse_path(i: &str) -> ParseResult<'_, Utf8PathBuf> {
+    map(
+        take_while1(|c: char| "abcdefghijklmnopqrstuvwxyz./".contains(c)),
+        Into::into,
+    )(i)
+}
+
+#[derive(Debug)]
+struct Ls;
+
+fn parse_ls(i: &str) -> ParseResult<'_, Ls> {
+    map(tag("ls"), |_| Ls)(i)
+}
+
+#[derive(Debug)]
+struct Cd(Utf8PathBuf);
+
+fn parse_cd(i: &str) -> ParseResult<'_, Cd> {
+    map(preceded(tag("cd "), parse_path), Cd)(i)
+}
+
+#[derive(Debug)]
+enum Command {
+    Ls,
+    Cd(Utf8PathBuf),
+}
+
+impl From<Ls> for Command {
+    fn from(_ls: Ls) -> Self {
+        Command::Ls
+    }
+}
+
+impl From<Cd> for Command {
+    fn from(cd: Cd) -> Self {
+        Command::Cd(cd.0)
+    }
+}
+
+fn parse_command(i: &str) -> ParseResult<'_, Command> {
+    let (i, _) = tag("$ ")(i)?;
+    alt((map(parse_ls, Into::into), map(parse_cd, Into::into)))(i)
+}
+
+// Entry names aren't needed: directories are only entered through `cd`, and
+// only file sizes count towards the totals.
+#[derive(Debug, Clone)]
+enum Entry {
+    Dir,
+    File(u64),
+}
+
+fn parse_entry(i: &str) -> ParseResult<'_, Entry> {
+    let parse_file = map(
+        separated_pair(nom::character::complete::u64, tag(" "), parse_path),
+        |(size, _path)| Entry::File(size),
+    );
+    let parse_dir = value(Entry::Dir, preceded(tag("dir "), parse_path));
+
+    alt((parse_file, parse_dir))(i)
+}
+
+#[derive(Debug)]
+enum Line {
+    Command(Command),
+    Entry(Entry),
+}
+
+fn parse_line(i: &str) -> ParseResult<'_, Line> {
+    alt((
+        map(parse_command, Line::Command),
+        map(parse_entry, Line::Entry),
+    ))(i)
+}
+
+#[derive(Debug)]
+pub struct FsEntry {
+    size: u64,
+}
+
+fn root(tree: &Tree<FsEntry>) -> miette::Result<&NodeId> {
+    tree.root_node_id()
+        .ok_or_else(|| miette::miette!("the file system has no root"))
+}
+
+/// The total size of every directory.
+fn dir_sizes(tree: &Tree<FsEntry>) -> miette::Result<Vec<u64>> {
+    tree.traverse_pre_order(root(tree)?)
+        .into_diagnostic()?
+        .filter(|node| !node.children().is_empty())
+        .map(|node| total_size(tree, node))
+        .collect()
+}
+
+fn total_size(tree: &Tree<FsEntry>, node: &Node<FsEntry>) -> miette::Result<u64> {
+    let mut total = node.data().size;
+
+    for child in node.children() {
+        total += total_size(tree, tree.get(child).into_diagnostic()?)?;
+    }
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part_one_example() {
+        let input = Day7::parse(EXAMPLE).unwrap();
+        assert_eq!(Day7::part_one(&input).unwrap(), 95437);
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day7::parse(EXAMPLE).unwrap();
+        assert_eq!(Day7::part_two(&input).unwrap(), 24933642);
+    }
+}
@@ -0,0 +1,135 @@
+use aoc_core::{
+    grid::{Grid, GridCoord},
+    parse::BadInput,
+    solution::Solution,
+};
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Input = Grid<usize>;
+    type AnswerOne = usize;
+    type AnswerTwo = usize;
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        Ok(parse_grid(input)?)
+    }
+
+    fn part_one(grid: &Self::Input) -> miette::Result<usize> {
+        let num_visible_cells = grid
+            .coords()
+            .filter(|&coord| {
+                let coord_height = grid.cell(coord).unwrap();
+                let deltas: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+                deltas.iter().any(|&(dx, dy)| {
+                    let mut cells_in_line = (1..).map_while(|i| {
+                        let coord = GridCoord {
+                            x: coord.x.checked_add_signed(dx * i)?,
+                            y: coord.y.checked_add_signed(dy * i)?,
+                        };
+                        grid.cell(coord)
+                    });
+                    cells_in_line.all(|height| height < coord_height)
+                })
+            })
+            .count();
+
+        Ok(num_visible_cells)
+    }
+
+    fn part_two(grid: &Self::Input) -> miette::Result<usize> {
+        let best_score = grid
+            .coords()
+            .map(|coord| scenic_score(grid, coord))
+            .max()
+            .ok_or_else(|| miette::miette!("no trees in input"))?;
+
+        Ok(best_score)
+    }
+}
+
+fn parse_grid(input: &str) -> Result<Grid<usize>, BadInput> {
+    let width = input.lines().next().map(str::len).unwrap_or_default();
+    let height = input.lines().count();
+
+    let mut grid = Grid::new(width, height);
+    for (y, line) in input.lines().enumerate() {
+        if line.len() != width {
+            return Err(BadInput::at(
+                input,
+                line,
+                format!("expected {width} trees, found {}", line.len()),
+            ));
+        }
+        for (x, col) in line.char_indices() {
+            if !col.is_ascii_digit() {
+                let bad = &line[x..x + col.len_utf8()];
+                return Err(BadInput::at(input, bad, "expected a tree height (0-9)"));
+            }
+            *grid.cell_mut((x, y).into()).unwrap() = col as usize - '0' as usize;
+        }
+    }
+
+    Ok(grid)
+}
+
+fn visible_trees_in_dir(
+    grid: &Grid<usize>,
+    coord: GridCoord,
+    (dir_x, dir_y): (isize, isize),
+) -> usize {
+    let line = (1..).map_while(|i| {
+        let coord = GridCoord {
+            x: coord.x.checked_add_signed(dir_x * i)?,
+            y: coord.y.checked_add_signed(dir_y * i)?,
+        };
+        Some(*grid.cell(coord)?)
+    });
+
+    let mut total = 0;
+    let our_height = *grid.cell(coord).unwrap();
+
+    for height in line {
+        total += 1;
+        if height >= our_height {
+            break;
+        }
+    }
+
+    total
+}
+
+fn scenic_score(grid: &Grid<usize>, coord: GridCoord) -> usize {
+    let dirs: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+    dirs.into_iter()
+        .map(|(dir_x, dir_y)| visible_trees_in_dir(grid, coord, (dir_x, dir_y)))
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part_one_example() {
+        let input = Day8::parse(EXAMPLE).unwrap();
+        assert_eq!(Day8::part_one(&input).unwrap(), 21);
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day8::parse(EXAMPLE).unwrap();
+        assert_eq!(Day8::part_two(&input).unwrap(), 8);
+    }
+
+    #[test]
+    fn scenic_scores() {
+        let grid = Day8::parse(EXAMPLE).unwrap();
+        assert_eq!(scenic_score(&grid, (2, 1).into()), 4);
+        assert_eq!(scenic_score(&grid, (2, 3).into()), 8);
+    }
+}
@@ -0,0 +1,117 @@
+use aoc_core::{
+    pos::{Direction, GridPos},
+    solution::Solution,
+};
+use day9::{Day9, Instruction, Simulation};
+use egui::{Color32, Sense, Stroke};
+
+use eframe::egui;
+
+fn main() -> miette::Result<()> {
+    let instructions = Day9::parse(&aoc_core::input!()?)?;
+    let options = eframe::NativeOptions {
+        initial_window_size: Some(egui::vec2(1280.0, 720.0)),
+        vsync: false,
+        ..Default::default()
+    };
+    eframe::run_native(
+        "AoC 2022 — Day 9",
+        options,
+        Box::new(move |_cc| Box::new(MyApp::new(&instructions))),
+    );
+    Ok(())
+}
+
+struct MyApp {
+    sim: Simulation,
+}
+
+impl MyApp {
+    fn new(instructions: &[Instruction]) -> Self {
+        Self {
+            sim: Simulation::new(instructions, 10),
+        }
+    }
+}
+
+impl eframe::App for MyApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.sim.update_state();
+
+        egui::SidePanel::left("side_panel").show(ctx, |ui| {
+            ui.label(format!("{} instructions left", self.sim.instructions.len()));
+            ui.label(format!("{} places visited", self.sim.tail_visited.len()));
+            egui::ScrollArea::new([false, true]).show(ui, |ui| {
+                for ins in &self.sim.instructions {
+                    let arrow = match ins.dir {
+                        Direction::Up => "⬆",
+                        Direction::Down => "⬇",
+                        Direction::Right => "➡",
+                        Direction::Left => "⬅",
+                    };
+                    ui.label(arrow.repeat(ins.dist as _));
+                }
+            })
+        });
+        egui::CentralPanel::default().show(ctx, |ui| {
+            const CANVAS_WIDTH: f32 = 900.0;
+            const CANVAS_HEIGHT: f32 = 700.0;
+            const SIDE: f32 = 5.0;
+
+            let painter_size = egui::vec2(CANVAS_WIDTH, CANVAS_HEIGHT);
+            let (res, painter) = ui.allocate_painter(painter_size, Sense::hover());
+            let center = res.rect.center().to_vec2();
+
+            let to_panel_pos = |pos: GridPos| {
+                (egui::vec2(pos.x as f32 * SIDE, pos.y as f32 * SIDE) + center).to_pos2()
+            };
+
+            let half_width = (CANVAS_WIDTH / SIDE).floor() as i32;
+            let half_height = (CANVAS_HEIGHT / SIDE).floor() as i32;
+
+            for x in -half_width..half_width {
+                for y in -half_height..half_height {
+                    let dot = GridPos { x, y };
+                    if !self.sim.tail_visited.contains(&dot) {
+                        continue;
+                    }
+                    let color = Color32::DARK_RED;
+
+                    let dot_pos = to_panel_pos(dot);
+                    painter.circle_stroke(dot_pos, 1.0, Stroke::new(2.0, color));
+                }
+            }
+
+            let num_knots = self.sim.knots.len();
+
+            for (i, knot_pos) in self.sim.knots.iter().copied().enumerate() {
+                let knot_pos = to_panel_pos(knot_pos);
+                if i > 0 {
+                    // paint an arrow from the previous knot to this one
+                    let prev_pos = to_panel_pos(self.sim.knots[i - 1]);
+                    painter.arrow(
+                        prev_pos,
+                        knot_pos - prev_pos,
+                        Stroke::new(1.0, Color32::YELLOW),
+                    )
+                }
+            }
+
+            for (i, knot_pos) in self.sim.knots.iter().copied().enumerate() {
+                let knot_pos = to_panel_pos(knot_pos);
+                painter.circle_filled(
+                    knot_pos,
+                    2.0,
+                    Color32::from_rgb(
+                        20,
+                        60 + ((255.0 - 60.0) * (num_knots as f32 - i as f32) / num_knots as f32)
+                            as u8,
+                        20,
+                    ),
+                );
+            }
+        });
+
+        ctx.request_repaint();
+    }
+}
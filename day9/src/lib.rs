@@ -0,0 +1,133 @@
+use std::collections::{HashSet, VecDeque};
+
+use aoc_core::{parse::parse_lines, pos::GridPos, solution::Solution};
+
+mod parse;
+pub use parse::Instruction;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Input = Vec<Instruction>;
+    type AnswerOne = usize;
+    type AnswerTwo = usize;
+
+    fn parse(input: &str) -> miette::Result<Self::Input> {
+        Ok(parse_lines(input, Instruction::parse).collect::<Result<_, _>>()?)
+    }
+
+    fn part_one(instructions: &Self::Input) -> miette::Result<usize> {
+        Ok(positions_visited_by_tail(instructions, 2))
+    }
+
+    fn part_two(instructions: &Self::Input) -> miette::Result<usize> {
+        Ok(positions_visited_by_tail(instructions, 10))
+    }
+}
+
+fn positions_visited_by_tail(instructions: &[Instruction], num_knots: usize) -> usize {
+    let mut sim = Simulation::new(instructions, num_knots);
+    while sim.update_state() {}
+    sim.tail_visited.len()
+}
+
+/// A rope being dragged around by its head, one step of one instruction at a
+/// time.
+pub struct Simulation {
+    pub instructions: VecDeque<Instruction>,
+    pub knots: Vec<GridPos>,
+    pub tail_visited: HashSet<GridPos>,
+}
+
+impl Simulation {
+    pub fn new(instructions: &[Instruction], num_knots: usize) -> Self {
+        Self {
+            instructions: instructions.iter().copied().collect(),
+            knots: vec![GridPos { x: 0, y: 0 }; num_knots],
+            tail_visited: Default::default(),
+        }
+    }
+
+    /// Moves the head one step. Returns `false` once there are no instructions
+    /// left.
+    pub fn update_state(&mut self) -> bool {
+        let instruction = match self.instructions.front_mut() {
+            Some(instruction) => instruction,
+            None => return false,
+        };
+        self.knots[0] += instruction.dir.delta();
+
+        for i in 1..self.knots.len() {
+            let diff = self.knots[i - 1] - self.knots[i];
+            let (dx, dy) = match (diff.x, diff.y) {
+                // overlapping
+                (0, 0) => (0, 0),
+                // touching up/left/down/right
+                (0, 1) | (1, 0) | (0, -1) | (-1, 0) => (0, 0),
+                // touching diagonally
+                (1, 1) | (1, -1) | (-1, 1) | (-1, -1) => (0, 0),
+                // need to move up/left/down/right
+                (0, 2) => (0, 1),
+                (0, -2) => (0, -1),
+                (2, 0) => (1, 0),
+                (-2, 0) => (-1, 0),
+                // need to move to the right diagonally
+                (2, 1) => (1, 1),
+                (2, -1) => (1, -1),
+                // need to move to the left diagonally
+                (-2, 1) => (-1, 1),
+                (-2, -1) => (-1, -1),
+                // need to move up/down diagonally
+                (1, 2) => (1, 1),
+                (-1, 2) => (-1, 1),
+                (1, -2) => (1, -1),
+                (-1, -2) => (-1, -1),
+                // 🆕 need to move diagonally
+                (-2, -2) => (-1, -1),
+                (-2, 2) => (-1, 1),
+                (2, -2) => (1, -1),
+                (2, 2) => (1, 1),
+                _ => panic!("unhandled case: tail - head = {diff:?}"),
+            };
+            self.knots[i].x += dx;
+            self.knots[i].y += dy;
+
+            if i == self.knots.len() - 1 {
+                self.tail_visited.insert(self.knots[i]);
+            }
+        }
+
+        instruction.dist -= 1;
+        if instruction.dist == 0 {
+            self.instructions.pop_front();
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part_one_example() {
+        let input = Day9::parse(EXAMPLE).unwrap();
+        assert_eq!(Day9::part_one(&input).unwrap(), 13);
+    }
+
+    #[test]
+    fn part_two_example() {
+        let input = Day9::parse(EXAMPLE).unwrap();
+        assert_eq!(Day9::part_two(&input).unwrap(), 1);
+    }
+
+    #[test]
+    fn part_two_larger_example() {
+        let input = Day9::parse(include_str!("example2.txt")).unwrap();
+        assert_eq!(Day9::part_two(&input).unwrap(), 36);
+    }
+}
@@ -1,98 +1,54 @@
+use aoc_core::{parse::ParseResult, pos::Direction};
 use nom::{
-    branch::alt,
-    bytes::complete::tag,
     character::complete::space1,
-    combinator::{map, value},
+    combinator::{map, verify},
+    error::context,
     sequence::{preceded, tuple},
-    IResult,
 };
-use std::fmt;
-
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-pub(crate) struct GridPos {
-    pub(crate) x: i32,
-    pub(crate) y: i32,
-}
-
-impl fmt::Debug for GridPos {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "({}, {})", self.x, self.y)
-    }
-}
-
-impl std::ops::Add for GridPos {
-    type Output = GridPos;
-
-    fn add(self, other: GridPos) -> GridPos {
-        GridPos {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        }
-    }
-}
-
-impl std::ops::AddAssign for GridPos {
-    fn add_assign(&mut self, other: GridPos) {
-        *self = GridPos {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        };
-    }
-}
-
-impl std::ops::Sub for GridPos {
-    type Output = GridPos;
-
-    fn sub(self, other: GridPos) -> GridPos {
-        GridPos {
-            x: self.x - other.x,
-            y: self.y - other.y,
-        }
-    }
-}
-
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-pub(crate) enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    pub(crate) fn parse(i: &str) -> IResult<&str, Self> {
-        alt((
-            value(Direction::Up, tag("U")),
-            value(Direction::Down, tag("D")),
-            value(Direction::Left, tag("L")),
-            value(Direction::Right, tag("R")),
-        ))(i)
-    }
-
-    pub(crate) fn delta(self) -> GridPos {
-        match self {
-            Direction::Up => GridPos { x: 0, y: -1 },
-            Direction::Down => GridPos { x: 0, y: 1 },
-            Direction::Left => GridPos { x: -1, y: 0 },
-            Direction::Right => GridPos { x: 1, y: 0 },
-        }
-    }
-}
 
 #[derive(Debug, Clone, Copy)]
-pub(crate) struct Instruction {
-    pub(crate) dir: Direction,
-    pub(crate) dist: u32,
+pub struct Instruction {
+    pub dir: Direction,
+    pub dist: u32,
 }
 
 impl Instruction {
-    pub(crate) fn parse(i: &str) -> IResult<&str, Self> {
+    pub fn parse(i: &str) -> ParseResult<'_, Self> {
         map(
             tuple((
                 Direction::parse,
-                preceded(space1, nom::character::complete::u32),
+                preceded(
+                    space1,
+                    context(
+                        "distance must be at least 1",
+                        verify(nom::character::complete::u32, |&dist| dist > 0),
+                    ),
+                ),
             )),
             |(dir, dist)| Self { dir, dist },
         )(i)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::parse::parse_lines;
+
+    #[test]
+    fn zero_distance_is_rejected() {
+        let err = parse_lines("R 4\nU 0", Instruction::parse)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err();
+        let labels = miette::Diagnostic::labels(&err)
+            .unwrap()
+            .filter_map(|l| l.label().map(str::to_owned))
+            .collect::<Vec<_>>();
+        assert!(
+            labels
+                .iter()
+                .any(|l| l.contains("distance must be at least 1")),
+            "{labels:?}"
+        );
+    }
+}